@@ -0,0 +1,269 @@
+use std::{collections::BTreeMap, fs, path::{Path, PathBuf}};
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use crate::{cli::Exit, misc::{output::OutputValue, toml_file}, runner::{DayRun, RunOutcome}, solver::Part};
+
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>
+}
+
+/// Confirmed answers of a single year,
+/// stored as `{answers}/{year}.toml` with a `[dayNN]` table per day.
+#[derive(Debug, Clone, Default)]
+pub struct Answers {
+    days: BTreeMap<u32, DayAnswers>
+}
+
+fn day_key(day: u32) -> String {
+    format!("day{day:0>2}")
+}
+
+pub fn answers_path(answers: &Path, year: i32) -> PathBuf {
+    answers.join(format!("{year}.toml"))
+}
+
+impl Answers {
+    /// Loads the answers of the given year, empty if there is no answers file yet.
+    pub fn load(answers: &Path, year: i32) -> Result<Self> {
+        let path = answers_path(answers, year);
+        let tables: BTreeMap<String, DayAnswers> = toml_file::load_or_default(&path, "answers file")?;
+
+        let mut days = BTreeMap::new();
+        for (key, answers) in tables {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| eyre!("Invalid day {key:?} in {}", path.display()))?;
+            days.insert(day, answers);
+        }
+
+        Ok(Self {
+            days
+        })
+    }
+
+    pub fn save(&self, answers: &Path, year: i32) -> Result<()> {
+        let tables = self.days
+            .iter()
+            .map(|(day, answers)| (day_key(*day), answers))
+            .collect::<BTreeMap<_, _>>();
+
+        let path = answers_path(answers, year);
+        fs::create_dir_all(answers)?;
+        fs::write(&path, toml::to_string(&tables)?)
+            .wrap_err_with(|| format!("Could not write {}", path.display()))
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        let answers = self.days.get(&day)?;
+        match part {
+            Part::One => answers.part1.as_deref(),
+            Part::Two => answers.part2.as_deref()
+        }
+    }
+
+    pub fn set(&mut self, day: u32, part: Part, answer: impl Into<String>) {
+        let answers = self.days.entry(day).or_default();
+        let answer = Some(answer.into());
+        match part {
+            Part::One => answers.part1 = answer,
+            Part::Two => answers.part2 = answer
+        }
+    }
+
+    /// Compares the given output with the stored answer.
+    pub fn check(&self, day: u32, part: Part, output: &OutputValue) -> Verdict {
+        Verdict::of(self.get(day, part), output)
+    }
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Mismatch {
+        expected: String,
+        actual: String
+    },
+    /// No answer is stored yet, contains the actual output.
+    Unknown(String)
+}
+
+impl Verdict {
+    /// Compares the given output with the expected answer, if there is one.
+    pub fn of(expected: Option<&str>, output: &OutputValue) -> Self {
+        let actual = output.to_string();
+        match expected {
+            None => Self::Unknown(actual),
+            Some(expected) if expected == actual => Self::Correct,
+            Some(expected) => Self::Mismatch {
+                expected: expected.to_string(),
+                actual
+            }
+        }
+    }
+}
+
+
+/// Number of days [`verify`] checked, skipped and found wrong.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Verification {
+    /// Days that were run and compared with their stored answers.
+    pub checked: usize,
+    /// Days that weren't run because they have no input.
+    pub skipped: usize,
+    /// Checked days that failed or had an answer that didn't match.
+    pub failed: usize
+}
+
+impl Verification {
+    /// Whether at least one day was checked, none were skipped and none failed.
+    pub fn passed(&self) -> bool {
+        self.checked > 0 && self.skipped == 0 && self.failed == 0
+    }
+
+    /// Code to exit with when the verification of `runs` didn't pass, the most severe problem first.
+    pub fn exit_code(&self, runs: &[DayRun]) -> Option<Exit> {
+        if self.passed() {
+            None
+        } else if runs.iter().any(DayRun::has_failed) {
+            Some(Exit::SolverError)
+        } else if self.failed > 0 {
+            Some(Exit::Mismatch)
+        } else if self.skipped > 0 {
+            Some(Exit::MissingInput)
+        } else {
+            Some(Exit::UnknownPuzzle)
+        }
+    }
+}
+
+
+/// Compares the answers of the given runs with the stored answers, printing the result of each part.
+/// When `record` is set, answers of parts that have none stored yet are saved.
+///
+/// A part without a stored answer doesn't fail its day, a day without input is skipped.
+pub fn verify(runs: &[DayRun], answers: &Path, record: bool) -> Result<Verification> {
+    let mut verification = Verification::default();
+    let mut years = runs.iter().map(|run| run.year).collect::<Vec<_>>();
+    years.dedup();
+
+    for year in years {
+        let mut stored = Answers::load(answers, year)?;
+        let mut changed = false;
+
+        for run in runs.iter().filter(|run| run.year == year) {
+            let prefix = format!("{year} day {:>2}", run.day);
+            let parts = match &run.outcome {
+                RunOutcome::Solved(parts) => parts,
+                RunOutcome::Failed(err) => {
+                    println!("{prefix}: failed, {err}");
+                    verification.checked += 1;
+                    verification.failed += 1;
+                    continue;
+                },
+                RunOutcome::TimedOut(timeout) => {
+                    println!("{prefix}: {timeout}");
+                    verification.checked += 1;
+                    verification.failed += 1;
+                    continue;
+                },
+                RunOutcome::MissingInput(path) => {
+                    println!("{prefix}: skipped, no input at {}", path.display());
+                    verification.skipped += 1;
+                    continue;
+                }
+            };
+
+            let mut correct = true;
+            for part in parts {
+                let output = match &part.result {
+                    Ok(output) => output,
+                    Err(err) => {
+                        println!("{prefix} part {}: failed, {err}", part.part);
+                        correct = false;
+                        continue;
+                    }
+                };
+
+                match stored.check(run.day, part.part, output) {
+                    Verdict::Correct => println!("{prefix} part {}: ok", part.part),
+                    Verdict::Mismatch { expected, actual } => {
+                        println!("{prefix} part {}: MISMATCH, expected {expected} but got {actual}", part.part);
+                        correct = false;
+                    },
+                    Verdict::Unknown(actual) if record => {
+                        println!("{prefix} part {}: recorded {actual}", part.part);
+                        stored.set(run.day, part.part, actual);
+                        changed = true;
+                    },
+                    Verdict::Unknown(actual) => println!("{prefix} part {}: no stored answer, got {actual}", part.part)
+                }
+            }
+
+            verification.checked += 1;
+            if !correct {
+                verification.failed += 1;
+            }
+        }
+
+        if changed {
+            stored.save(answers, year)?;
+        }
+    }
+
+    Ok(verification)
+}
+
+
+#[test]
+fn test() {
+    use crate::{misc::test::temp_dir, runner::PartRun};
+
+    let dir = temp_dir("answers");
+    let mut answers = Answers::default();
+    answers.set(1, Part::One, "11");
+    answers.set(1, Part::Two, "31");
+    answers.set(12, Part::Two, "abc");
+    answers.save(&dir, 2024).unwrap();
+
+    let loaded = Answers::load(&dir, 2024).unwrap();
+    assert_eq!(loaded.get(1, Part::One), Some("11"));
+    assert_eq!(loaded.get(12, Part::Two), Some("abc"));
+    assert_eq!(loaded.get(12, Part::One), None);
+    assert!(Answers::load(&dir, 2023).unwrap().days.is_empty());
+
+    let run = |day, outcome| DayRun::test(day, true, outcome);
+    let solved = |part1: u32, part2: u32| RunOutcome::Solved(vec![
+        PartRun::test(Part::One, Ok(part1.into()), 0),
+        PartRun::test(Part::Two, Ok(part2.into()), 0)
+    ]);
+
+    let matching = verify(&[run(1, solved(11, 31))], &dir, false).unwrap();
+    assert_eq!(matching, Verification { checked: 1, skipped: 0, failed: 0 });
+    assert!(matching.passed());
+    assert_eq!(matching.exit_code(&[run(1, solved(11, 31))]), None);
+
+    let mismatch = verify(&[run(1, solved(11, 30))], &dir, false).unwrap();
+    assert_eq!(mismatch.failed, 1);
+    assert!(!mismatch.passed());
+    assert_eq!(mismatch.exit_code(&[run(1, solved(11, 30))]), Some(Exit::Mismatch));
+
+    let missing = verify(&[run(1, solved(11, 31)), run(2, RunOutcome::MissingInput(dir.join("day02.txt")))], &dir, false).unwrap();
+    assert_eq!(missing, Verification { checked: 1, skipped: 1, failed: 0 });
+    assert!(!missing.passed());
+    assert_eq!(missing.exit_code(&[]), Some(Exit::MissingInput));
+    assert!(!verify(&[], &dir, false).unwrap().passed());
+    assert_eq!(Verification::default().exit_code(&[]), Some(Exit::UnknownPuzzle));
+    let failed = [run(4, RunOutcome::Failed(eyre!("bad input")))];
+    assert_eq!(verify(&failed, &dir, false).unwrap().exit_code(&failed), Some(Exit::SolverError));
+
+    verify(&[run(3, solved(5, 6))], &dir, true).unwrap();
+    assert_eq!(Answers::load(&dir, 2024).unwrap().get(3, Part::Two), Some("6"));
+    fs::remove_dir_all(&dir).unwrap();
+}
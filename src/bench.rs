@@ -0,0 +1,241 @@
+use std::{collections::BTreeMap, fs, io::{BufReader, Cursor}, path::Path, time::Duration};
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use crate::{misc::toml_file, params::Params, runner::{self, RunOutcome}, solver::{Part, Solver}, Input};
+
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub max: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration
+}
+
+impl Stats {
+    pub fn new(samples: &mut [Duration]) -> Self {
+        samples.sort();
+        let count = samples.len() as f64;
+        let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>() / count;
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Self {
+            min: samples[0],
+            max: samples[samples.len() - 1],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt())
+        }
+    }
+}
+
+
+/// Statistics of each stage of a day, in the order they run.
+/// Days solving both parts at once only have a total.
+pub type BenchResult = Vec<(&'static str, Stats)>;
+
+fn input_from(bytes: &[u8]) -> Input {
+    BufReader::new(Box::new(Cursor::new(bytes.to_vec())))
+}
+
+/// Runs a day `warmup` times without measuring, then `runs` times measuring each stage.
+/// Every run reads the same input from memory.
+pub fn bench(solver: &dyn Solver, input: &[u8], params: &Params, parts: &[Part], warmup: usize, runs: usize) -> Result<BenchResult> {
+    let mut samples: BTreeMap<&'static str, Vec<Duration>> = BTreeMap::new();
+
+    for i in 0..warmup + runs {
+        let run = runner::run_day(solver, input_from(input), params, parts);
+        let part_runs = match run.outcome {
+            RunOutcome::Solved(ref part_runs) => part_runs,
+            RunOutcome::Failed(err) => return Err(err),
+            RunOutcome::MissingInput(_) => unreachable!("Input is in memory"),
+            RunOutcome::TimedOut(_) => unreachable!("Benchmarks have no timeout")
+        };
+        if let Some(err) = part_runs.iter().find_map(|part_run| part_run.result.as_ref().err()) {
+            return Err(eyre!("{err}"));
+        }
+        if i < warmup {
+            continue;
+        }
+
+        if run.separate_parts {
+            samples.entry("parse").or_default().push(run.parse_duration);
+            for part_run in part_runs {
+                let stage = match part_run.part {
+                    Part::One => "part 1",
+                    Part::Two => "part 2"
+                };
+                samples.entry(stage).or_default().push(part_run.duration);
+            }
+        }
+        samples.entry("total").or_default().push(run.total_duration());
+    }
+
+    // the stage names already sort in the order they run
+    Ok(samples
+        .into_iter()
+        .map(|(stage, mut samples)| (stage, Stats::new(&mut samples)))
+        .collect())
+}
+
+
+/// Mean duration in nanoseconds of each stage of each benchmarked day, keyed by `{year}-d{NN}`,
+/// with the part like `{year}-d{NN}-p1` when only one part was run.
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    days: BTreeMap<String, BTreeMap<String, u64>>
+}
+
+fn day_key(year: i32, day: u32, parts: &[Part]) -> String {
+    match parts {
+        [part] => format!("{year}-d{day:0>2}-p{part}"),
+        _ => format!("{year}-d{day:0>2}")
+    }
+}
+
+impl Baseline {
+    /// Loads the baseline, empty if there is no baseline file yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(Self {
+            days: toml_file::load_or_default(path, "baseline file")?
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(&self.days)?)
+            .wrap_err_with(|| format!("Could not write {}", path.display()))
+    }
+
+    pub fn get(&self, year: i32, day: u32, parts: &[Part], stage: &str) -> Option<Duration> {
+        self.days
+            .get(&day_key(year, day, parts))?
+            .get(stage)
+            .map(|nanos| Duration::from_nanos(*nanos))
+    }
+
+    pub fn has(&self, year: i32, day: u32, parts: &[Part]) -> bool {
+        self.days.contains_key(&day_key(year, day, parts))
+    }
+
+    pub fn set(&mut self, year: i32, day: u32, parts: &[Part], result: &BenchResult) {
+        self.days.insert(day_key(year, day, parts), result
+            .iter()
+            .map(|(stage, stats)| (stage.to_string(), stats.mean.as_nanos() as u64))
+            .collect());
+    }
+}
+
+
+/// Percentage `mean` is slower than `base`, negative when faster,
+/// and whether that is more than `threshold` percent. `None` without a base to compare to.
+pub fn compare(mean: Duration, base: Duration, threshold: f64) -> Option<(f64, bool)> {
+    if base.is_zero() {
+        return None;
+    }
+    let change = (mean.as_secs_f64() / base.as_secs_f64() - 1.0) * 100.0;
+    Some((change, change > threshold))
+}
+
+/// What [`check_baseline`] found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BaselineCheck {
+    /// Whether any stage got slower than the baseline by more than the threshold.
+    pub regressed: bool,
+    /// Whether the result was stored as the new baseline.
+    pub saved: bool
+}
+
+/// Prints the result of the solver's day compared with the baseline at `path`, then stores it as the baseline
+/// of the day when `update` is set or the baseline has no result of the day with the same parts yet.
+pub fn check_baseline(path: &Path, solver: &dyn Solver, parts: &[Part], runs: usize, result: &BenchResult, threshold: f64, update: bool) -> Result<BaselineCheck> {
+    let (year, day) = (solver.year(), solver.day());
+    let mut baseline = Baseline::load(path)?;
+    let regressed = print_result(year, day, parts, runs, result, &baseline, threshold);
+
+    let saved = update || !baseline.has(year, day, parts);
+    if saved {
+        baseline.set(year, day, parts, result);
+        baseline.save(path)?;
+    }
+    Ok(BaselineCheck { regressed, saved })
+}
+
+/// Prints the statistics of each stage, compared with the baseline if it has this day with the same parts.
+///
+/// Returns whether any stage got slower than the baseline by more than `threshold` percent.
+pub fn print_result(year: i32, day: u32, parts: &[Part], runs: usize, result: &BenchResult, baseline: &Baseline, threshold: f64) -> bool {
+    let mut regressed = false;
+
+    println!("Day {day} of year {year}, {runs} runs");
+    println!(
+        "{:<8}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "Stage", "Min", "Max", "Median", "Mean", "Stddev", "Baseline"
+    );
+    for (stage, stats) in result {
+        let comparison = match baseline.get(year, day, parts, stage).and_then(|base| compare(stats.mean, base, threshold)) {
+            Some((change, true)) => {
+                regressed = true;
+                format!("{change:+.1}% REGRESSION")
+            },
+            Some((change, false)) => format!("{change:+.1}%"),
+            None => "-".to_string()
+        };
+
+        println!(
+            "{stage:<8}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}",
+            format!("{:.2?}", stats.min), format!("{:.2?}", stats.max), format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean), format!("{:.2?}", stats.stddev),
+            comparison
+        );
+    }
+
+    regressed
+}
+
+
+#[test]
+fn test() {
+    let millis = Duration::from_millis;
+
+    let stats = Stats::new(&mut [millis(4), millis(2), millis(6), millis(4)]);
+    assert_eq!((stats.min, stats.max, stats.median, stats.mean), (millis(2), millis(6), millis(4), millis(4)));
+    assert_eq!(stats.stddev.as_micros(), 1414);
+    assert_eq!(Stats::new(&mut [millis(3), millis(1), millis(2)]).median, millis(2));
+
+    assert_eq!(compare(millis(105), millis(100), 10.0).map(|(_, regressed)| regressed), Some(false));
+    assert_eq!(compare(millis(120), millis(100), 10.0).map(|(_, regressed)| regressed), Some(true));
+    assert!(compare(millis(50), millis(100), 10.0).unwrap().0 < 0.0);
+    assert_eq!(compare(millis(50), Duration::ZERO, 10.0), None);
+
+    let result = vec![("total", stats)];
+    let mut baseline = Baseline::default();
+    baseline.set(2024, 1, &[Part::One], &result);
+    assert!(baseline.has(2024, 1, &[Part::One]));
+    assert!(!baseline.has(2024, 1, &Part::ALL), "Baselines of different parts aren't compared");
+    assert_eq!(baseline.get(2024, 1, &[Part::One], "total"), Some(millis(4)));
+
+    let dir = crate::misc::test::temp_dir("bench");
+    let path = dir.join("baseline.toml");
+    let solver = crate::solver::find(2024, 1).unwrap();
+    let check = |result: &BenchResult, update| check_baseline(&path, solver, &Part::ALL, 4, result, 10.0, update).unwrap();
+    assert_eq!(check(&result, false), BaselineCheck { regressed: false, saved: true });
+    assert_eq!(check(&result, false), BaselineCheck { regressed: false, saved: false });
+
+    let slower = vec![("total", Stats::new(&mut [millis(8)]))];
+    assert_eq!(check(&slower, false), BaselineCheck { regressed: true, saved: false });
+    assert_eq!(check(&slower, true), BaselineCheck { regressed: true, saved: true });
+    assert!(!check(&result, false).regressed, "Faster than the updated baseline");
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,110 @@
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+use color_eyre::eyre::{bail, Result};
+
+
+/// Puzzles unlock at midnight US-Eastern, which is always UTC-5 in December.
+const UNLOCK_OFFSET: i32 = 5 * 60 * 60;
+pub const FIRST_YEAR: i32 = 2015;
+
+
+/// Number of puzzles of the given event, 12 since 2025.
+pub fn day_count(year: i32) -> u32 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+/// The moment the given puzzle unlocks.
+pub fn unlock_time(year: i32, day: u32) -> DateTime<Utc> {
+    FixedOffset::west_opt(UNLOCK_OFFSET)
+        .expect("Unlock offset is out of bounds")
+        .with_ymd_and_hms(year, 12, day, 0, 0, 0)
+        .single()
+        .expect("Puzzle days are valid December dates")
+        .to_utc()
+}
+
+pub fn is_unlocked(year: i32, day: u32, now: DateTime<Utc>) -> bool {
+    now >= unlock_time(year, day)
+}
+
+
+/// Fails with a message explaining why if the given year has no event, or doesn't have the given day.
+pub fn validate(year: i32, day: u32) -> Result<()> {
+    if year < FIRST_YEAR {
+        bail!("There is no event in {year}, the first one was in {FIRST_YEAR}");
+    }
+    let count = day_count(year);
+    if day == 0 || day > count {
+        bail!("Day {day} is out of range, {year} has days 1 to {count}");
+    }
+    Ok(())
+}
+
+/// The newest puzzle that has unlocked, of the given year if given.
+pub fn newest_unlocked(year: Option<i32>, now: DateTime<Utc>) -> Result<(i32, u32)> {
+    let year = match year {
+        Some(year) => year,
+        // the event of this year starts in December, before that the newest puzzle is from last year
+        None if is_unlocked(now.year(), 1, now) => now.year(),
+        None => now.year() - 1
+    };
+    validate(year, 1)?;
+
+    match (1..=day_count(year)).rev().find(|day| is_unlocked(year, *day, now)) {
+        Some(day) => Ok((year, day)),
+        None => bail!("No puzzle of {year} has unlocked yet, the first unlocks at {}", unlock_time(year, 1))
+    }
+}
+
+/// The given puzzle if it exists, defaulting to the newest unlocked one.
+pub fn resolve(year: Option<i32>, day: Option<u32>, now: DateTime<Utc>) -> Result<(i32, u32)> {
+    match (year, day) {
+        (Some(year), Some(day)) => {
+            validate(year, day)?;
+            Ok((year, day))
+        },
+        (None, Some(day)) => {
+            let (year, _) = newest_unlocked(None, now)?;
+            validate(year, day)?;
+            Ok((year, day))
+        },
+        (year, None) => newest_unlocked(year, now)
+    }
+}
+
+/// Fails like [`resolve`] if the given year or day doesn't exist, for selecting the days of multiple puzzles.
+pub fn validate_selection(year: Option<i32>, day: Option<u32>, now: DateTime<Utc>) -> Result<()> {
+    match (year, day) {
+        (Some(_), _) => resolve(year, day, now).map(|_| ()),
+        (None, Some(day)) if day == 0 || day > day_count(FIRST_YEAR) => {
+            bail!("Day {day} is out of range, events have at most days 1 to {}", day_count(FIRST_YEAR))
+        },
+        (None, _) => Ok(())
+    }
+}
+
+
+#[test]
+fn test() {
+    let at = |month, day, hour| Utc.with_ymd_and_hms(2025, month, day, hour, 0, 0).unwrap();
+
+    assert_eq!(resolve(None, None, at(12, 3, 4)).unwrap(), (2025, 2), "Day 3 unlocks at 05:00 UTC");
+    assert_eq!(resolve(None, None, at(12, 3, 5)).unwrap(), (2025, 3));
+    assert_eq!(resolve(None, None, at(12, 31, 0)).unwrap(), (2025, 12), "2025 only has 12 days");
+    assert_eq!(resolve(None, None, at(11, 30, 0)).unwrap(), (2024, 25));
+    assert_eq!(resolve(Some(2024), None, at(6, 1, 0)).unwrap(), (2024, 25));
+    assert_eq!(resolve(None, Some(13), at(6, 1, 0)).unwrap(), (2024, 13));
+
+    assert!(resolve(Some(2025), None, at(6, 1, 0)).is_err(), "2025 hasn't started yet");
+    assert!(resolve(Some(2025), Some(13), at(12, 31, 0)).is_err());
+    assert!(resolve(Some(2014), Some(1), at(12, 31, 0)).is_err());
+
+    assert!(validate_selection(Some(2023), None, at(12, 31, 0)).is_ok());
+    assert!(validate_selection(None, Some(25), at(6, 1, 0)).is_ok());
+    assert!(validate_selection(None, Some(30), at(12, 31, 0)).is_err());
+    assert!(validate_selection(Some(2014), None, at(12, 31, 0)).is_err());
+    assert!(validate_selection(Some(2026), None, at(12, 31, 0)).is_err());
+}
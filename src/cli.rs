@@ -0,0 +1,150 @@
+use std::{io::Read, path::Path, process};
+use chrono::{DateTime, Utc};
+use crate::{calendar, params::Params, report::{ErrorKind, ErrorReport}, runner, solver::{self, Solver}, Input};
+
+
+/// Exit codes of the process besides 0 for success, documented by [`EXIT_CODES`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    /// Anything else that went wrong, like a failed download.
+    Failure = 1,
+    /// Invalid arguments, the same code clap uses.
+    Usage = 2,
+    /// A solver returned an error, panicked or timed out.
+    SolverError = 3,
+    /// The year or day doesn't exist, hasn't unlocked yet or has no solution.
+    UnknownPuzzle = 4,
+    MissingInput = 5,
+    /// An answer didn't match the stored or expected answer, or was rejected.
+    Mismatch = 6
+}
+
+impl Exit {
+    pub fn exit(self) -> ! {
+        process::exit(self as i32)
+    }
+}
+
+pub const EXIT_CODES: &str = "Exit codes:
+  0  Success
+  1  Other failure, like a failed download
+  2  Invalid arguments
+  3  A solver failed, panicked or timed out
+  4  Unknown year or day, or a day without a solution
+  5  Input not found
+  6  An answer didn't match the expected answer";
+
+
+/// An error that stops a command, with the code the process exits with.
+pub struct Failure {
+    pub code: Exit,
+    /// Year and day the error is about, when it is about a single puzzle.
+    pub puzzle: Option<(i32, u32)>,
+    pub error: ErrorReport
+}
+
+impl Failure {
+    pub fn new(code: Exit, error: ErrorReport) -> Self {
+        Self {
+            code,
+            puzzle: None,
+            error
+        }
+    }
+
+    pub fn of_puzzle(self, year: i32, day: u32) -> Self {
+        Self {
+            puzzle: Some((year, day)),
+            ..self
+        }
+    }
+}
+
+
+/// The given year and day, defaulting to the newest unlocked puzzle.
+pub fn resolve_puzzle(year: Option<i32>, day: Option<u32>, now: DateTime<Utc>) -> Result<(i32, u32), Failure> {
+    calendar::resolve(year, day, now)
+        .map_err(|err| Failure::new(Exit::UnknownPuzzle, ErrorReport::from_report(ErrorKind::UnknownPuzzle, &err)))
+}
+
+/// Solvers of the given year and day, or all of them when not given.
+/// Fails if the year or day doesn't exist or nothing has a solution.
+pub fn select_solvers(year: Option<i32>, day: Option<u32>, now: DateTime<Utc>) -> Result<Vec<&'static dyn Solver>, Failure> {
+    calendar::validate_selection(year, day, now)
+        .map_err(|err| Failure::new(Exit::UnknownPuzzle, ErrorReport::from_report(ErrorKind::UnknownPuzzle, &err)))?;
+
+    let solvers = solver::select(year, day);
+    if solvers.is_empty() {
+        let message = year.map_or("Given day has no solution", no_solution_message);
+        return Err(Failure::new(Exit::UnknownPuzzle, ErrorReport::new(ErrorKind::NoSolution, message)));
+    }
+    Ok(solvers)
+}
+
+/// The solver of the given day.
+pub fn find_solver(year: i32, day: u32) -> Result<&'static dyn Solver, Failure> {
+    solver::find(year, day).ok_or_else(|| {
+        Failure::new(Exit::UnknownPuzzle, ErrorReport::new(ErrorKind::NoSolution, no_solution_message(year))).of_puzzle(year, day)
+    })
+}
+
+fn no_solution_message(year: i32) -> &'static str {
+    if solver::years().contains(&year) {
+        "Given day has no solution"
+    } else {
+        "Given year has no solutions"
+    }
+}
+
+/// Parameters of the solver with the given overrides, failing if one of them doesn't exist.
+pub fn solver_params(solver: &dyn Solver, overrides: &[(String, String)]) -> Result<Params, Failure> {
+    Params::new(solver.params(), overrides).map_err(|err| {
+        Failure::new(Exit::Usage, ErrorReport::from_report(ErrorKind::InvalidParams, &err)).of_puzzle(solver.year(), solver.day())
+    })
+}
+
+/// Opens the input of a day like [`runner::open_input`], falling back to piped stdin.
+pub fn open_input(explicit: Option<&Path>, inputs: &Path, year: i32, day: u32) -> Result<Input, Failure> {
+    runner::open_input(explicit, inputs, year, day, runner::piped_stdin()).map_err(|err| {
+        Failure::new(Exit::MissingInput, ErrorReport::from_report(ErrorKind::MissingInput, &err)).of_puzzle(year, day)
+    })
+}
+
+/// Reads the whole input of a day into memory, like [`open_input`].
+pub fn read_input(explicit: Option<&Path>, inputs: &Path, year: i32, day: u32) -> Result<Vec<u8>, Failure> {
+    let mut input = Vec::new();
+    open_input(explicit, inputs, year, day)?
+        .read_to_end(&mut input)
+        .map_err(|err| {
+            let error = ErrorReport::new(ErrorKind::MissingInput, format!("Could not read input: {err}"));
+            Failure::new(Exit::MissingInput, error).of_puzzle(year, day)
+        })?;
+    Ok(input)
+}
+
+
+#[test]
+fn test() {
+    use chrono::TimeZone;
+    use crate::misc::test::temp_dir;
+
+    let now = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+    let code = |failure: Failure| (failure.code, failure.puzzle);
+
+    assert_eq!(resolve_puzzle(Some(2024), Some(1), now).ok(), Some((2024, 1)));
+    assert_eq!(resolve_puzzle(Some(2025), Some(13), now).map_err(code).err(), Some((Exit::UnknownPuzzle, None)));
+    assert!(select_solvers(Some(2024), Some(1), now).is_ok_and(|solvers| solvers.len() == 1));
+    assert_eq!(select_solvers(None, Some(30), now).map_err(code).err(), Some((Exit::UnknownPuzzle, None)));
+    assert_eq!(find_solver(2015, 1).map_err(code).err(), Some((Exit::UnknownPuzzle, Some((2015, 1)))));
+
+    let solver = find_solver(2024, 14).ok().unwrap();
+    let overrides = [("depth".to_string(), "3".to_string())];
+    assert_eq!(solver_params(solver, &overrides).map_err(code).err(), Some((Exit::Usage, Some((2024, 14)))));
+
+    let inputs = temp_dir("cli");
+    let missing = inputs.join("missing.txt");
+    assert_eq!(read_input(Some(&missing), &inputs, 2024, 1).map_err(code).err(), Some((Exit::MissingInput, Some((2024, 1)))));
+    std::fs::write(&missing, "3 4\n").unwrap();
+    assert_eq!(read_input(Some(&missing), &inputs, 2024, 1).ok(), Some(b"3 4\n".to_vec()));
+    std::fs::remove_dir_all(inputs).unwrap();
+}
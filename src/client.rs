@@ -0,0 +1,140 @@
+use std::{env, fs, path::{Path, PathBuf}, time::Duration};
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use crate::{calendar, runner::day_input_path, solver::Part};
+
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/ketrab2004/advent-of-code-2024";
+/// File aoc-cli reads the session token from, inside the home directory.
+const SESSION_FILE: &str = ".adventofcode.session";
+
+
+/// Client for the Advent of Code website, or anything serving the same routes.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build()
+        }
+    }
+
+    fn url(&self, year: i32, day: u32, path: &str) -> String {
+        format!("{}/{year}/day/{day}{path}", self.base_url)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn fetch_input(&self, year: i32, day: u32) -> Result<String> {
+        let response = self.agent
+            .get(&self.url(year, day, "/input"))
+            .set("Cookie", &self.cookie())
+            .call();
+
+        match response {
+            Ok(response) => response.into_string().wrap_err("Could not read the downloaded input"),
+            Err(ureq::Error::Status(404, _)) => bail!("Day {day} of {year} has no input, is it released yet?"),
+            Err(ureq::Error::Status(400 | 401 | 500, _)) => bail!("The server rejected the session token, is it expired?"),
+            Err(err) => Err(eyre!(err)).wrap_err_with(|| format!("Could not download the input of day {day} of {year}"))
+        }
+    }
+
+    /// Posts an answer, returning the page the server responds with.
+    pub fn submit_answer(&self, year: i32, day: u32, part: Part, answer: &str) -> Result<String> {
+        let response = self.agent
+            .post(&self.url(year, day, "/answer"))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        match response {
+            Ok(response) => response.into_string().wrap_err("Could not read the response to the answer"),
+            Err(ureq::Error::Status(404, _)) => bail!("Day {day} of {year} doesn't exist, is it released yet?"),
+            Err(ureq::Error::Status(400 | 401 | 500, _)) => bail!("The server rejected the session token, is it expired?"),
+            Err(err) => Err(eyre!(err)).wrap_err_with(|| format!("Could not submit the answer of day {day} of {year}"))
+        }
+    }
+}
+
+
+/// The given session token, or the one stored in `~/.adventofcode.session` like aoc-cli does.
+pub fn find_session(session: Option<&str>) -> Result<String> {
+    if let Some(session) = session {
+        return Ok(session.to_string());
+    }
+
+    env::var_os("HOME")
+        .map(|home| Path::new(&home).join(SESSION_FILE))
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+        .ok_or_else(|| eyre!("No session token, pass --session, set AOC_SESSION or store it in ~/{SESSION_FILE}"))
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already in the inputs directory.
+    Cached(PathBuf),
+    Downloaded(PathBuf)
+}
+
+/// Makes sure the input of the given day is in the inputs directory,
+/// only downloading it if it isn't there yet and the puzzle has unlocked.
+pub fn fetch_input(base_url: &str, session: Option<&str>, inputs: &Path, year: i32, day: u32, now: DateTime<Utc>) -> Result<Fetched> {
+    let path = day_input_path(inputs, year, day);
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    if !calendar::is_unlocked(year, day, now) {
+        bail!("Day {day} of {year} unlocks at {}, not fetching it yet", calendar::unlock_time(year, day));
+    }
+
+    let client = Client::new(base_url, &find_session(session)?);
+    let input = client.fetch_input(year, day)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, input).wrap_err_with(|| format!("Could not write {}", path.display()))?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+
+#[test]
+fn test() {
+    use chrono::TimeZone;
+    use crate::misc::test::{mock_server, temp_dir};
+
+    let (url, requests) = mock_server(|request| match request.line.as_str() {
+        "GET /2024/day/1/input HTTP/1.1" => (200, "3   4\n4   3\n".to_string()),
+        _ => (404, "Not found".to_string())
+    });
+    let inputs = temp_dir("fetch");
+    let now = Utc.with_ymd_and_hms(2024, 12, 1, 5, 0, 0).unwrap();
+
+    let path = day_input_path(&inputs, 2024, 1);
+    assert_eq!(fetch_input(&url, Some("token"), &inputs, 2024, 1, now).unwrap(), Fetched::Downloaded(path.clone()));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "3   4\n4   3\n");
+    assert_eq!(fetch_input(&url, Some("token"), &inputs, 2024, 1, now).unwrap(), Fetched::Cached(path));
+    assert!(fetch_input(&url, Some("token"), &inputs, 2024, 2, now).is_err(), "Day 2 is not unlocked yet");
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].headers.iter().any(|header| header == "Cookie: session=token"));
+
+    fs::remove_dir_all(inputs).unwrap();
+}
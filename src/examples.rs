@@ -0,0 +1,124 @@
+use std::{collections::BTreeMap, io::{BufReader, Cursor}, path::{Path, PathBuf}};
+use color_eyre::eyre::{Result, WrapErr};
+use serde::Deserialize;
+use crate::{answers::Verdict, misc::toml_file, params::Params, runner::{self, DayRun, RunOutcome}, solver::{Part, Solver}, Input};
+
+
+/// A named example input of a day, with the answers it should give.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    /// Parameters the example needs other than the defaults, like a smaller grid size.
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+    pub part1: Option<String>,
+    pub part2: Option<String>
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Fixture {
+    #[serde(default, rename = "example")]
+    examples: Vec<Example>
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref()
+        }
+    }
+
+    pub fn input(&self) -> Input {
+        BufReader::new(Box::new(Cursor::new(self.input.clone().into_bytes())))
+    }
+
+    /// Runs the given parts of the day on this example, with the example's parameters.
+    pub fn run(&self, solver: &dyn Solver, parts: &[Part]) -> Result<DayRun> {
+        let overrides = self.params.iter().collect::<Vec<_>>();
+        let params = Params::new(solver.params(), &overrides)
+            .wrap_err_with(|| format!("Invalid parameters in example {}", self.name))?;
+        Ok(runner::run_day(solver, self.input(), &params, parts))
+    }
+}
+
+
+/// Location of the examples of a day inside the fixtures directory,
+/// `{fixtures}/{year}/day{NN}.toml` with an `[[example]]` table per example.
+pub fn fixture_path(fixtures: &Path, year: i32, day: u32) -> PathBuf {
+    fixtures
+        .join(year.to_string())
+        .join(format!("day{day:0>2}.toml"))
+}
+
+/// Loads the examples of a day, none if the day has no fixture.
+pub fn load(fixtures: &Path, year: i32, day: u32) -> Result<Vec<Example>> {
+    let fixture: Fixture = toml_file::load_or_default(&fixture_path(fixtures, year, day), "fixture")?;
+    Ok(fixture.examples)
+}
+
+
+/// Runs the examples of the given solvers, printing the result of each part.
+/// Parts without an expected answer in the example are skipped.
+///
+/// Returns whether every part gave its expected answer.
+pub fn run_all(solvers: &[&'static dyn Solver], fixtures: &Path, parts: &[Part]) -> Result<bool> {
+    let mut all_correct = true;
+    let mut found = 0;
+
+    for solver in solvers {
+        for example in load(fixtures, solver.year(), solver.day())? {
+            found += 1;
+            let prefix = format!("{} day {:>2} {}", solver.year(), solver.day(), example.name);
+            let parts = parts
+                .iter()
+                .copied()
+                .filter(|part| example.expected(*part).is_some())
+                .collect::<Vec<_>>();
+
+            let run = match example.run(*solver, &parts) {
+                Ok(run) => run,
+                Err(err) => {
+                    println!("{prefix}: {err}");
+                    all_correct = false;
+                    continue;
+                }
+            };
+            let part_runs = match &run.outcome {
+                RunOutcome::Solved(part_runs) => part_runs,
+                RunOutcome::Failed(err) => {
+                    println!("{prefix}: failed, {err}");
+                    all_correct = false;
+                    continue;
+                },
+                RunOutcome::MissingInput(_) | RunOutcome::TimedOut(_) => unreachable!("Examples are run directly")
+            };
+
+            for part in part_runs {
+                let verdict = match &part.result {
+                    Ok(output) => Verdict::of(example.expected(part.part), output),
+                    Err(err) => {
+                        println!("{prefix} part {}: failed, {err}", part.part);
+                        all_correct = false;
+                        continue;
+                    }
+                };
+
+                match verdict {
+                    Verdict::Correct => println!("{prefix} part {}: ok", part.part),
+                    Verdict::Mismatch { expected, actual } => {
+                        println!("{prefix} part {}: MISMATCH, expected {expected} but got {actual}", part.part);
+                        all_correct = false;
+                    },
+                    Verdict::Unknown(_) => unreachable!("Only parts with an expected answer are run")
+                }
+            }
+        }
+    }
+
+    if found == 0 {
+        println!("No examples found in {}", fixtures.display());
+    }
+    Ok(all_correct)
+}
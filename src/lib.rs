@@ -0,0 +1,65 @@
+//! Advent of Code solutions, with the helpers they share and a registry of every solved day.
+//!
+//! [`solve`] runs a day with its default parameters, the [`solver`] module lists and finds the registered days.
+
+use std::io::{BufReader, Read};
+use color_eyre::eyre::{eyre, Result};
+use misc::output::OutputValue;
+use params::Params;
+use runner::RunOutcome;
+use solver::Part;
+
+// solutions of each year, `new` adds the module of a new year after these
+mod solutions;
+mod solutions2;
+pub mod misc;
+pub mod answers;
+pub mod bench;
+pub mod calendar;
+pub mod cli;
+pub mod client;
+pub mod examples;
+pub mod logging;
+pub mod memory;
+pub mod params;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solver;
+pub mod submit;
+pub mod watch;
+
+
+pub type Input = BufReader<Box<dyn Read + Send>>;
+pub type Output = Result<(OutputValue, OutputValue)>;
+pub type PartOutput = Result<OutputValue>;
+/// `output!(part)` for the output of a single part, `output!(part1, part2)` for both.
+#[macro_export]
+macro_rules! output {
+    ($part:expr) => {
+        Ok($crate::misc::output::OutputValue::from($part))
+    };
+    ($part1:expr, $part2:expr) => {
+        Ok(($crate::misc::output::OutputValue::from($part1), $crate::misc::output::OutputValue::from($part2)))
+    };
+}
+
+
+/// Solves a day with its default parameters, returning the answer of part 1 and of part 2 if the day has one.
+///
+/// Panics of the solver are returned as errors.
+pub fn solve(year: i32, day: u32, input: impl Read + Send + 'static) -> Result<(OutputValue, Option<OutputValue>)> {
+    let solver = solver::find(year, day).ok_or_else(|| eyre!("Day {day} of {year} has no solution"))?;
+    let input: Input = BufReader::new(Box::new(input));
+    let run = runner::run_day(solver, input, &Params::defaults(solver.params()), &Part::ALL);
+
+    let parts = match run.outcome {
+        RunOutcome::Solved(parts) => parts,
+        RunOutcome::Failed(err) => return Err(err),
+        RunOutcome::MissingInput(_) | RunOutcome::TimedOut(_) => unreachable!("Input is given and there is no timeout")
+    };
+    let mut results = parts.into_iter().map(|part| part.result);
+    let part1 = results.next().ok_or_else(|| eyre!("Day {day} of {year} has no part 1"))??;
+    let part2 = results.next().transpose()?;
+    Ok((part1, part2))
+}
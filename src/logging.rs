@@ -0,0 +1,43 @@
+use env_logger::Builder;
+use indicatif_log_bridge::LogWrapper;
+use log::LevelFilter;
+use crate::misc::progress::MULTI_PROGRESS;
+
+
+/// Environment variable with filter directives on top of the verbosity,
+/// like `2024::d14=debug` to see the diagnostics of a single day.
+///
+/// Every day logs with its own target, `{year}::d{day}`.
+pub const FILTER_ENV: &str = "AOC_LOG";
+
+/// Level of the messages shown for a verbosity, the number of `-v`s minus the number of `-q`s.
+pub fn level(verbosity: i8) -> LevelFilter {
+    match verbosity {
+        ..=-2 => LevelFilter::Off,
+        -1 => LevelFilter::Error,
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        3.. => LevelFilter::Trace
+    }
+}
+
+/// Sets up logging to stderr at the level of the verbosity, refined by [`FILTER_ENV`].
+///
+/// Messages are drawn above the progress bars, so they don't get drawn over.
+pub fn init(verbosity: i8) {
+    let mut builder = Builder::new();
+    builder
+        .filter_level(level(verbosity))
+        .format_timestamp(None);
+    if let Ok(filters) = std::env::var(FILTER_ENV) {
+        builder.parse_filters(&filters);
+    }
+
+    let logger = builder.build();
+    let max_level = logger.filter();
+    LogWrapper::new(MULTI_PROGRESS.clone(), logger)
+        .try_init()
+        .expect("Logger was already set up");
+    log::set_max_level(max_level);
+}
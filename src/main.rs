@@ -1,82 +1,536 @@
-use std::{cmp::{min, Ordering}, fs::File, io::{self, BufReader, Read}, path::PathBuf};
-use clap::{crate_authors, crate_description, crate_version, Parser};
-use chrono::{Datelike, Utc};
-use color_eyre::eyre::Result;
-use misc::output::OutputValue;
-
-mod solutions;
-mod solutions2;
-mod misc;
-
-
-pub type Input = BufReader<Box<dyn Read>>;
-pub type Output = Result<(OutputValue, OutputValue)>;
-#[macro_export]
-macro_rules! output {
-    ($part1:expr) => {
-        Ok(($crate::misc::output::OutputValue::from($part1), $crate::misc::output::OutputValue::from(-1i32)))
-    };
-    ($part1:expr, $part2:expr) => {
-        Ok(($crate::misc::output::OutputValue::from($part1), $crate::misc::output::OutputValue::from($part2)))
-    };
-}
+use std::{fs::File, io::BufReader, path::{Path, PathBuf}, time::Duration};
+use clap::{crate_authors, crate_description, crate_version, ArgAction, Parser, Subcommand};
+use chrono::Utc;
+use advent_of_code_2024::{
+    answers, bench, cli::{self, Exit, Failure, EXIT_CODES}, client::{self, Fetched}, examples, logging, memory::{self, CountingAllocator},
+    params, report::{self, DayReport, Format}, runner::{self, DayRun, RunOutcome}, scaffold, solver::{self, Part}, submit, watch
+};
+
 
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 
 #[derive(Parser, Debug)]
-#[command(author=crate_authors!(", "), version=crate_version!(), about=crate_description!(), long_about = None)]
+#[command(author=crate_authors!(", "), version=crate_version!(), about=crate_description!(), long_about = None, after_long_help = EXIT_CODES)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(
-        short, long,
+        short, long, global = true,
         num_args = 0..=1, require_equals = true,
-        default_value = None, default_missing_value = "./input",
+        default_value = None, default_missing_value = "-",
         help = "Source of puzzle input",
-        long_help = "stdin by default, --input without an explicit path defaults to ./input"
+        long_help = "The day's file in --inputs by default, or stdin when it is piped and the file doesn't exist. \
+            --input without an explicit path, or --input=-, reads stdin"
     )]
     input: Option<PathBuf>,
 
-    #[arg(short, long, help = "Year of puzzle")]
+    #[arg(short, long, global = true, help = "Year of puzzle")]
     year: Option<i32>,
 
-    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25), help = "Day of puzzle")]
+    #[arg(
+        short, long, global = true,
+        help = "Day of puzzle",
+        long_help = "Day of puzzle, the newest unlocked puzzle of --year by default. Events up to 2024 have 25 days, later ones 12"
+    )]
     day: Option<u32>,
+
+    #[arg(
+        short, long, global = true,
+        value_parser = clap::value_parser!(u8).range(1..=2),
+        help = "Only run the given part",
+        long_help = "Only run the given part, both by default. Days solving both parts at once still do so"
+    )]
+    part: Option<u8>,
+
+    #[arg(
+        short, long,
+        conflicts_with_all = ["input", "day"],
+        help = "Run every day and print a summary",
+        long_help = "Runs every day of the given --year, or of all years when not given, reading inputs from --inputs"
+    )]
+    all: bool,
+
+    #[arg(
+        long, global = true, env = "AOC_INPUTS", default_value = "./inputs",
+        help = "Directory of puzzle inputs",
+        long_help = "Each input is read from {inputs}/{year}/day{NN}.txt"
+    )]
+    inputs: PathBuf,
+
+    #[arg(
+        long, global = true, default_value = "./answers",
+        help = "Directory of confirmed answers and submissions",
+        long_help = "The answers of each year are stored in {answers}/{year}.toml, submissions in {answers}/submissions.toml"
+    )]
+    answers: PathBuf,
+
+    #[arg(
+        long, global = true, default_value = "./fixtures",
+        help = "Directory of example inputs",
+        long_help = "The examples of each day are stored in {fixtures}/{year}/day{NN}.toml, \
+            as [[example]] tables with a name, input, optional params and the expected part1 and part2"
+    )]
+    fixtures: PathBuf,
+
+    #[arg(
+        long, global = true, value_enum, default_value_t = Format::Text,
+        help = "Format of the results",
+        long_help = "Format of the results of a single day or --all, json includes each part's value type, timings and errors. \
+            Errors that stop a run before it starts, like an unknown day, are a JSON object with an error too"
+    )]
+    format: Format,
+
+    #[arg(
+        long, global = true, env = "AOC_SESSION", hide_env_values = true,
+        help = "Session token used to talk to the Advent of Code website",
+        long_help = "Session token used to talk to the Advent of Code website, read from ~/.adventofcode.session when not given"
+    )]
+    session: Option<String>,
+
+    #[arg(
+        long, global = true, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL,
+        help = "Base url of the Advent of Code website"
+    )]
+    base_url: String,
+
+    #[arg(
+        long = "param", global = true, value_name = "KEY=VALUE", value_parser = params::parse_override,
+        help = "Override a parameter of the day, can be given multiple times",
+        long_help = "Override a parameter of the day, like the grid size of a day whose example is smaller than the real puzzle. \
+            Can be given multiple times, list shows the parameters of each day"
+    )]
+    params: Vec<(String, String)>,
+
+    #[arg(
+        short, long, global = true,
+        help = "Number of days run at the same time",
+        long_help = "Number of days --all and verify run at the same time, one per core by default. \
+            Use 1 for timings that aren't affected by other days"
+    )]
+    jobs: Option<usize>,
+
+    #[arg(
+        long, global = true, value_name = "SECONDS", value_parser = parse_seconds,
+        help = "Give up on a day after this many seconds",
+        long_help = "Give up on a day after this many seconds, reporting how far its progress bar got. \
+            --all and verify continue with the next day"
+    )]
+    timeout: Option<Duration>,
+
+    #[arg(
+        long, global = true,
+        help = "Count the allocations of every part",
+        long_help = "Count the allocations, allocated bytes and the high-water mark of the live heap of parsing and every part. \
+            Slows down allocating a little, and makes --all run one day at a time"
+    )]
+    profile_mem: bool,
+
+    #[arg(
+        short, long, global = true, action = ArgAction::Count,
+        help = "Show more of what the solvers log, repeat for more",
+        long_help = "Show more of what the solvers log on stderr: info, debug with -vv and trace with -vvv. \
+            AOC_LOG takes filters per day on top, like AOC_LOG=2024::d14=debug"
+    )]
+    verbose: u8,
+
+    #[arg(
+        short, long, global = true, action = ArgAction::Count, conflicts_with = "verbose",
+        help = "Only show errors, or nothing with -qq"
+    )]
+    quiet: u8,
+
+    #[arg(
+        long, global = true,
+        help = "Run again whenever the input changes",
+        long_help = "Keep running, and run the day again whenever its input or --watch-example changes. \
+            Errors and panics are reported without stopping"
+    )]
+    watch: bool,
+
+    #[arg(long, global = true, value_name = "FILE", requires = "watch", help = "Example input to also run on with --watch")]
+    watch_example: Option<PathBuf>,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    #[command(about = "List the puzzles that have a solution, of --year if given")]
+    List,
+
+    #[command(
+        about = "Check the answers of the selected days against the stored answers",
+        long_about = "Reruns --day of --year, or every day when not given, and compares each answer with the stored one. \
+            Exits with a non-zero code if any answer doesn't match, a day fails or has no input, or no day was checked"
+    )]
+    Verify {
+        #[arg(long, help = "Store the answers of parts that have no stored answer yet")]
+        record: bool
+    },
+
+    #[command(
+        about = "Run the examples of the selected days and check their answers",
+        long_about = "Runs the examples in --fixtures of --day of --year, or of every day when not given, \
+            and compares each answer with the expected one. \
+            Exits with a non-zero code if any answer doesn't match or an example fails"
+    )]
+    Examples,
+
+    #[command(
+        about = "Download the input of --day of --year into --inputs",
+        long_about = "Download the input of --day of --year into --inputs, today's puzzle by default. \
+            Inputs that were already downloaded are never downloaded again, and puzzles that haven't unlocked yet are refused"
+    )]
+    Fetch,
+
+    #[command(
+        about = "Solve --part of --day of --year and submit the answer",
+        long_about = "Solve --part of --day of --year and submit the answer, today's puzzle by default. \
+            Every submission is logged in --answers, answers known to be wrong or outside the known bounds are never submitted"
+    )]
+    Submit,
+
+    #[command(
+        about = "Create the file of a new day from the template",
+        long_about = "Create the file of --day of --year from the template, with a parse stage, both parts and an example test. \
+            The first day of a year also adds the year's module. An existing file is never overwritten"
+    )]
+    New {
+        #[arg(long, help = "Title of the puzzle, \"Day {day}\" by default")]
+        title: Option<String>,
+
+        #[arg(long, default_value = ".", help = "Root of the crate to create the day in")]
+        root: PathBuf
+    },
+
+    #[command(
+        about = "Measure how long --day of --year takes over multiple runs",
+        long_about = "Runs --day of --year after warming up, reading the input into memory once, \
+            and prints the min, median, mean and standard deviation of parsing and each part. \
+            Compares the means with the baseline of the same --part, exiting with a non-zero code on a regression"
+    )]
+    Bench {
+        #[arg(short, long, default_value_t = 10, help = "Number of measured runs")]
+        runs: usize,
+
+        #[arg(short, long, default_value_t = 2, help = "Number of runs before measuring")]
+        warmup: usize,
+
+        #[arg(
+            long, default_value_t = 10.0,
+            help = "Percentage a mean may be slower than the baseline before it is a regression"
+        )]
+        threshold: f64,
+
+        #[arg(long, default_value = "./bench/baseline.toml", help = "File the baseline means are stored in")]
+        baseline: PathBuf,
+
+        #[arg(
+            long,
+            help = "Replace the baseline of the day with this run",
+            long_help = "Replace the baseline of the day with this run, it is only stored automatically if the day had none"
+        )]
+        update_baseline: bool
+    }
+}
+
+
+
+fn parse_seconds(arg: &str) -> Result<Duration, String> {
+    match arg.parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!("expected a positive number of seconds, got {arg:?}"))
+    }
+}
+
+/// Prints the error that stops the program, as a JSON report with `--format json`, and exits with its code.
+/// The report is of the puzzle the error is about if it is known.
+fn fail(format: Format, failure: Failure) -> ! {
+    match (format, failure.puzzle) {
+        (Format::Text, _) => {
+            eprintln!("{}", failure.error.message);
+            for cause in &failure.error.causes {
+                eprintln!("\nCaused by: {cause}");
+            }
+        },
+        (Format::Json, Some((year, day))) => report::print_day(&DayReport::failed(year, day, failure.error)),
+        (Format::Json, None) => report::print_error(failure.error)
+    }
+    failure.code.exit();
+}
+
+trait OrFail<T> {
+    /// The value, or [`fail`] with the error.
+    fn or_fail(self, format: Format) -> T;
+}
+
+impl<T> OrFail<T> for Result<T, Failure> {
+    fn or_fail(self, format: Format) -> T {
+        self.unwrap_or_else(|failure| fail(format, failure))
+    }
+}
 
 
 fn main() {
     color_eyre::install().expect("Failed to install color_eyre");
     let args = Args::parse();
+    logging::init(args.verbose.min(3) as i8 - args.quiet.min(2) as i8);
+    if args.profile_mem {
+        memory::enable();
+    }
+    if let Some(jobs) = args.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .expect("Failed to configure the rayon pool");
+    }
+
+    let parts = match args.part.and_then(Part::from_number) {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec()
+    };
+
+    match args.command {
+        Some(Command::List) => {
+            for solver in solver::select(args.year, None) {
+                println!("{} day {:>2}: {}", solver.year(), solver.day(), solver.title());
+                for param in solver.params() {
+                    println!("    --param {:<16} {}", param.to_string(), param.help);
+                }
+            }
+            return;
+        },
+        Some(Command::Verify { record }) => {
+            let solvers = cli::select_solvers(args.year, args.day, Utc::now()).or_fail(Format::Text);
+            let runs = runner::run_all(&solvers, &args.inputs, &parts, args.timeout);
+            match answers::verify(&runs, &args.answers, record) {
+                Ok(verification) => {
+                    eprintln!(
+                        "\nChecked {} days, skipped {} without input, {} failed",
+                        verification.checked, verification.skipped, verification.failed
+                    );
+                    if let Some(code) = verification.exit_code(&runs) {
+                        eprintln!("Verification failed");
+                        code.exit();
+                    }
+                },
+                Err(err) => {
+                    eprintln!("Could not verify answers\n\nCaused by: {}\n\n{:?}", err, err);
+                    Exit::Failure.exit();
+                }
+            }
+            return;
+        },
+        Some(Command::Examples) => {
+            let solvers = cli::select_solvers(args.year, args.day, Utc::now()).or_fail(Format::Text);
+            match examples::run_all(&solvers, &args.fixtures, &parts) {
+                Ok(true) => return,
+                Ok(false) => {
+                    eprintln!("\nExamples failed");
+                    Exit::Mismatch.exit();
+                },
+                Err(err) => {
+                    eprintln!("Could not run examples\n\nCaused by: {}\n\n{:?}", err, err);
+                    Exit::Failure.exit();
+                }
+            }
+        },
+        Some(Command::Fetch) => {
+            let (year, day) = cli::resolve_puzzle(args.year, args.day, Utc::now()).or_fail(Format::Text);
+            match client::fetch_input(&args.base_url, args.session.as_deref(), &args.inputs, year, day, Utc::now()) {
+                Ok(Fetched::Cached(path)) => eprintln!("Input of day {day} of {year} is already at {}", path.display()),
+                Ok(Fetched::Downloaded(path)) => eprintln!("Downloaded input of day {day} of {year} to {}", path.display()),
+                Err(err) => {
+                    eprintln!("Could not fetch input: {err:#}");
+                    Exit::Failure.exit();
+                }
+            }
+            return;
+        },
+        Some(Command::Submit) => {
+            let (year, day) = cli::resolve_puzzle(args.year, args.day, Utc::now()).or_fail(Format::Text);
+            let Some(part) = args.part.and_then(Part::from_number) else {
+                eprintln!("Submitting needs a --part");
+                Exit::Usage.exit();
+            };
+            let solver = cli::find_solver(year, day).or_fail(Format::Text);
+            let params = cli::solver_params(solver, &args.params).or_fail(Format::Text);
+            let input = cli::open_input(args.input.as_deref(), &args.inputs, year, day).or_fail(Format::Text);
 
-    let now = Utc::now();
-    let mut year = args.year.unwrap_or(now.year());
-    let day = args.day.unwrap_or_else(|| match (year.cmp(&now.year()), now.month()) {
-        (Ordering::Less, _) => 25,
-        (Ordering::Greater, _) => 1,
-        (Ordering::Equal, 12) => min(now.day(), 25),
-        (Ordering::Equal, _) => if args.year.is_none() {
-            year = now.year() - 1;
-            25
-        } else {
-            1
+            let run = runner::run_day_with_timeout(solver, input, params, &[part], String::new(), args.timeout);
+            let answer = submit::part_answer(&run, part).or_fail(Format::Text);
+            eprintln!("Submitting {answer} as the answer of part {part} of day {day} of {year}");
+            match submit::submit(&args.base_url, args.session.as_deref(), &args.answers, year, day, part, answer) {
+                Ok(reply) => {
+                    println!("{}", reply.message);
+                    match reply.bounds {
+                        (Some(lower), Some(upper)) => println!("The answer must be > {lower} and < {upper}"),
+                        (Some(lower), None) => println!("The answer must be > {lower}"),
+                        (None, Some(upper)) => println!("The answer must be < {upper}"),
+                        (None, None) => ()
+                    }
+                    if let Some(code) = reply.exit_code() {
+                        code.exit();
+                    }
+                },
+                Err(err) => {
+                    eprintln!("Could not submit: {err:#}");
+                    Exit::Failure.exit();
+                }
+            }
+            return;
+        },
+        Some(Command::New { title, root }) => {
+            let (Some(year), Some(day)) = (args.year, args.day) else {
+                eprintln!("Creating a new day needs both a --year and a --day");
+                Exit::Usage.exit();
+            };
+            let title = title.unwrap_or_else(|| format!("Day {day}"));
+            match scaffold::new_day(&root, year, day, &title) {
+                Ok(path) => eprintln!("Created {}", path.display()),
+                Err(err) => {
+                    eprintln!("Could not create day: {err:#}");
+                    Exit::Failure.exit();
+                }
+            }
+            return;
+        },
+        Some(Command::Bench { runs, warmup, threshold, baseline, update_baseline }) => {
+            let (year, day) = cli::resolve_puzzle(args.year, args.day, Utc::now()).or_fail(Format::Text);
+            let solver = cli::find_solver(year, day).or_fail(Format::Text);
+            let params = cli::solver_params(solver, &args.params).or_fail(Format::Text);
+            let input = cli::read_input(args.input.as_deref(), &args.inputs, year, day).or_fail(Format::Text);
+
+            let runs = runs.max(1);
+            let result = match bench::bench(solver, &input, &params, &parts, warmup, runs) {
+                Ok(result) => result,
+                Err(err) => {
+                    eprintln!("Something went wrong, is the input valid?\n\nCaused by: {}\n\n{:?}", err, err);
+                    Exit::SolverError.exit();
+                }
+            };
+
+            match bench::check_baseline(&baseline, solver, &parts, runs, &result, threshold, update_baseline) {
+                Ok(check) => {
+                    if check.saved {
+                        eprintln!("\nSaved baseline to {}", baseline.display());
+                    }
+                    if check.regressed {
+                        eprintln!("\nSlower than the baseline by more than {threshold}%");
+                        Exit::Failure.exit();
+                    }
+                },
+                Err(err) => {
+                    eprintln!("Could not use baseline: {err:#}");
+                    Exit::Failure.exit();
+                }
+            }
+            return;
+        },
+        None => ()
+    }
+
+    if args.all {
+        let solvers = cli::select_solvers(args.year, None, Utc::now()).or_fail(args.format);
+        let runs = runner::run_all(&solvers, &args.inputs, &parts, args.timeout);
+        match args.format {
+            Format::Text => {
+                runner::print_summary(&runs);
+                if args.profile_mem {
+                    runner::print_memory(&runs);
+                }
+            },
+            Format::Json => report::print_all(&runs)
         }
-    });
+        if runs.iter().any(DayRun::has_failed) {
+            Exit::SolverError.exit();
+        }
+        return;
+    }
 
-    let input: BufReader<Box<dyn Read>> = match args.input {
-        Some(path)  => BufReader::new(Box::new(File::open(path).expect("Could not open file"))),
-        None => BufReader::new(Box::new(io::stdin()))
-    };
+    let (year, day) = cli::resolve_puzzle(args.year, args.day, Utc::now()).or_fail(args.format);
+    if args.format == Format::Text {
+        eprintln!("Running day {day} of year {year}");
+    }
+    let solver = cli::find_solver(year, day).or_fail(args.format);
+    let params = cli::solver_params(solver, &args.params).or_fail(args.format);
 
-    println!("Running day {day} of year {year}");
-    let result = match year {
-        2024 => solutions::solve_day(day, input),
-        2025 => solutions2::solve_day(day, input),
-        _ => None
-    };
+    if args.watch {
+        let input = match &args.input {
+            Some(path) if path == Path::new("-") => {
+                eprintln!("Can't watch stdin, pass an input file instead");
+                Exit::Usage.exit();
+            },
+            Some(path) => path.clone(),
+            None => runner::day_input_path(&args.inputs, year, day)
+        };
+        let files = [Some(input), args.watch_example.clone()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        watch::watch(&files, |path| {
+            if files.len() > 1 && args.format == Format::Text {
+                eprintln!("{}:", path.display());
+            }
+            let file = match File::open(path) {
+                Ok(file) => file,
+                Err(err) => {
+                    eprintln!("Could not open input {}: {err}", path.display());
+                    return;
+                }
+            };
+
+            let run = runner::run_day_with_timeout(solver, BufReader::new(Box::new(file)), params.clone(), &parts, String::new(), args.timeout);
+            let total = run.total_duration();
+            print_run(run, args.format);
+            if args.format == Format::Text {
+                eprintln!("took {total:.2?}");
+            }
+        });
+    }
+
+    let input = cli::open_input(args.input.as_deref(), &args.inputs, year, day).or_fail(args.format);
+    let run = runner::run_day_with_timeout(solver, input, params, &parts, String::new(), args.timeout);
+    let failed = run.has_failed();
+    print_run(run, args.format);
+    if failed {
+        Exit::SolverError.exit();
+    }
+}
+
+
+/// Prints the answers of a single day, or its report as JSON.
+fn print_run(run: DayRun, format: Format) {
+    if format == Format::Json {
+        report::print_day(&DayReport::new(&run));
+        return;
+    }
 
-    match result {
-        Some(Ok((part1, part2))) => println!("part 1: {}\npart 2: {}", part1, part2),
-        Some(Err(err)) => println!("Something went wrong, is the input valid?\n\nCaused by: {}\n\n{:?}", err, err),
-        None => println!("Given year has no solutions")
+    // days solving both parts at once allocate everything while parsing
+    let (parse_memory, day_memory) = match run.separate_parts {
+        true => (run.parse_memory, None),
+        false => (None, run.parse_memory)
+    };
+    if let Some(memory) = parse_memory {
+        eprintln!("parse memory: {memory}");
+    }
+    match run.outcome {
+        RunOutcome::Solved(parts) => for part in parts {
+            match part.result {
+                Ok(value) => println!("part {}: {}", part.part, value),
+                Err(err) => eprintln!("Something went wrong in part {}, is the input valid?\n\nCaused by: {}\n\n{:?}", part.part, err, err)
+            }
+            if let Some(memory) = part.memory {
+                eprintln!("part {} memory: {memory}", part.part);
+            }
+        },
+        RunOutcome::Failed(err) => eprintln!("Something went wrong, is the input valid?\n\nCaused by: {}\n\n{:?}", err, err),
+        RunOutcome::MissingInput(_) => unreachable!("Input is opened before running"),
+        RunOutcome::TimedOut(timeout) => eprintln!("The solver {timeout}")
+    }
+    if let Some(memory) = day_memory {
+        eprintln!("memory: {memory}");
     }
 }
@@ -0,0 +1,148 @@
+use std::{alloc::{GlobalAlloc, Layout, System}, fmt::{self, Display}, sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering}};
+use serde::Serialize;
+
+
+/// The system allocator, counting allocations while profiling is enabled.
+///
+/// Only counts when it is the `#[global_allocator]` of the binary.
+pub struct CountingAllocator;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+/// Bytes currently allocated since profiling was enabled, negative when memory from before then is freed.
+static LIVE: AtomicI64 = AtomicI64::new(0);
+static PEAK: AtomicI64 = AtomicI64::new(0);
+
+impl CountingAllocator {
+    fn count_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size as u64, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size as i64, Ordering::Relaxed) + size as i64;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn count_dealloc(size: usize) {
+        LIVE.fetch_sub(size as i64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            Self::count_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            Self::count_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if ENABLED.load(Ordering::Relaxed) {
+            Self::count_dealloc(layout.size());
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            Self::count_dealloc(layout.size());
+            Self::count_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+
+/// Starts counting allocations, which costs a little time for every allocation.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+
+/// Allocations made while running something.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct MemoryStats {
+    pub allocations: u64,
+    /// Total size of all allocations, including the memory that was freed again.
+    pub bytes: u64,
+    /// High-water mark of the live heap, on top of what was allocated before.
+    /// Only counts the requested sizes, not resident memory like stacks or the allocator's overhead.
+    pub peak_heap_bytes: u64
+}
+
+/// Runs `f`, counting the allocations it makes if profiling is enabled.
+///
+/// Allocations are counted for the whole process, so nothing else should run at the same time.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    if !is_enabled() {
+        return (f(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let result = f();
+
+    (result, Some(MemoryStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: ALLOCATED.load(Ordering::Relaxed) - allocated,
+        // negative when nothing was allocated on top of the memory live at the start
+        peak_heap_bytes: u64::try_from(PEAK.load(Ordering::Relaxed).saturating_sub(live)).unwrap_or(0)
+    }))
+}
+
+
+/// Size in bytes with a binary unit, like `1.50 MiB`.
+pub struct Bytes(pub u64);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+
+        let mut size = self.0 as f64;
+        let mut unit = "B";
+        for next in UNITS {
+            if size < 1024.0 {
+                break;
+            }
+            size /= 1024.0;
+            unit = next;
+        }
+        write!(f, "{size:.2} {unit}")
+    }
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} allocations, {} allocated, {} peak heap", self.allocations, Bytes(self.bytes), Bytes(self.peak_heap_bytes))
+    }
+}
+
+
+#[test]
+fn test() {
+    assert_eq!(Bytes(512).to_string(), "512 B");
+    assert_eq!(Bytes(1536).to_string(), "1.50 KiB");
+    assert_eq!(Bytes(3 * 1024 * 1024).to_string(), "3.00 MiB");
+
+    // counting is tested in tests/memory.rs, where it can't slow down other tests
+    assert!(measure(|| ()).1.is_none());
+}
@@ -1,15 +1,18 @@
-use core::str;
-use std::fmt::{Debug, Display};
+use std::{array, fmt::{Debug, Display}};
 use color_eyre::eyre::Result;
 use error_rules::Error;
-use super::option::OptionExt;
+use super::{option::OptionExt, vector2::Directions};
 
 
-#[derive(Clone)]
-pub struct Grid {
+/// Rectangular grid of cells, stored row by row.
+///
+/// `Grid` without a cell type is a grid of ASCII characters, which can be created from lines of text and displayed as them.
+/// [`map`](Grid::map) converts between grids of different cell types.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Grid<T = u8> {
     width: usize,
     height: usize,
-    data: String
+    data: Vec<T>
 }
 
 #[derive(Debug, Clone, Copy, Error)]
@@ -17,150 +20,49 @@ pub enum GridError {
     #[error_kind("Grid: Inserted line has different width than grid")]
     DifferentWidth,
     #[error_kind("Grid: Inserted line is wider than grid")]
-    LargerWidth
+    LargerWidth,
+    #[error_kind("Grid: Cells don't fill whole rows")]
+    IncompleteRow
 }
 
 
 #[allow(dead_code)]
-impl Grid {
-    /// Adds a single line to the grid, should not contain newlines for proper formatting.
-    ///
-    /// Fails if the line has different width than the grid.
-    pub fn add_line(&mut self, line: impl AsRef<str>) -> Result<(), GridError> {
-        let line: &str = line.as_ref();
-        if line.len() != self.width {
-            return Err(GridError::DifferentWidth);
-        }
-
-        self.height += 1;
-        self.data.reserve(self.width + 1);
-        if self.height > 1 {
-            self.data.push('\n');
-        }
-        self.data.push_str(line);
-
-        Ok(())
-    }
-
-    /// Creates from iterator of lines,
-    /// using the width of the first line.
-    ///
-    /// Fails if not all lines have the same width.
-    pub fn from(mut input: impl Iterator<Item = impl AsRef<str>>) -> Result<Self, GridError> {
-        let first = input.next().unwrap();
-        let line: &str = first.as_ref();
-        let mut grid = Self {
-            width: line.len(),
-            height: 0,
-            data: String::with_capacity(line.len() + 1)
-        };
-
-        grid.add_line(line)?;
-        for line in input {
-            grid.add_line(line)?;
+impl<T: Copy> Grid<T> {
+    pub fn from_size(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![fill; width * height]
         }
-
-        Ok(grid)
     }
 
-    /// Create grid using the given string as body.
+    /// Creates from cells stored row by row.
     ///
-    /// Each line should be the same width,
-    /// ending newline is automatically removed.
-    pub fn from_string(mut input: String) -> Result<Self> {
-        let mut lines = input.lines();
-        let width  = lines.next().unwrap_or_err()?.len();
-        let mut height = 1;
-        for line in lines {
-            if line.len() != width {
-                return Err(GridError::DifferentWidth.into());
-            }
-            height += 1;
-        }
-        if width > 0 && input.ends_with('\n') {
-            input.pop();
+    /// Fails if the cells don't fill whole rows of the given width.
+    pub fn from_vec(width: usize, data: Vec<T>) -> Result<Self, GridError> {
+        if width == 0 || !data.len().is_multiple_of(width) {
+            return Err(GridError::IncompleteRow);
         }
 
         Ok(Self {
             width,
-            height,
-            data: input
+            height: data.len() / width,
+            data
         })
     }
 
-    /// Adds a line to the grid, should not contain newlines for proper formatting.
-    ///
-    /// Fails if the line is wider than the grid, otherwise is filled to width.
-    /// Spaces by default.
-    pub fn add_line_with_fill(&mut self, line: impl AsRef<str>, fill: Option<char>) -> Result<(), GridError> {
-        let line: &str = line.as_ref();
-        if line.len() > self.width {
-            return Err(GridError::LargerWidth);
-        }
-        let fill = fill.unwrap_or(' ');
-
-        self.height += 1;
-        self.data.reserve(self.width + 1);
-        if self.height > 1 {
-            self.data.push('\n');
-        }
-        self.data.push_str(line);
-        for _ in 0..(self.width - line.len()) {
-            self.data.push(fill);
-        }
-
-        Ok(())
-    }
-
-    /// Creates from iterator of lines,
-    /// using the given width.
-    /// Padding with fill to reach it, spaces by default.
-    ///
-    /// Fails if any line is longer than the given width.
-    pub fn with_fill(input: impl Iterator<Item = impl AsRef<str>>, width: usize, fill: Option<char>) -> Result<Self, GridError> {
-        let mut grid = Self {
-            width,
-            height: 0,
-            data: String::with_capacity(width)
-        };
-
-        for line in input {
-            grid.add_line_with_fill(line, fill)?;
-        }
-
-        Ok(grid)
-    }
-
-    /// Creates from iterator of lines.
-    ///
-    /// Gets the width of the widest line and pads each line with the given fill to reach it.
-    pub fn with_dynamic_width(input: impl Iterator<Item = impl AsRef<str>> + Clone, fill: Option<char>) -> Self {
-        let mut width = 0;
-        for line in input.clone() {
-            let len = line.as_ref().len();
-            if len > width {
-                width = len;
-            }
-        }
-
-        Self::with_fill(input, width, fill)
-            .expect("Width of input changed after first iteration")
+    /// The cells row by row.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
     }
 
-    pub fn from_size(width: usize, height: usize, fill: u8) -> Self {
-        let mut grid = Self {
-            width,
-            height: 0,
-            data: String::with_capacity((width + 1) * height - 1)
-        };
-        let mut line = String::from(char::from(fill));
-        line = line.repeat(width);
-
-        for _ in 0..height {
-            grid.add_line(&line).expect("Repeated fill char str couldn't be added to grid");
+    /// Creates a grid of the same size, with each cell converted by `f`.
+    pub fn map<U: Copy>(&self, f: impl FnMut(T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            data: self.data.iter().copied().map(f).collect()
         }
-
-        grid
     }
 
 
@@ -168,19 +70,26 @@ impl Grid {
         (self.width, self.height)
     }
 
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on a width of 0, which only empty grids have
+        self.data.chunks(self.width.max(1))
+    }
+
 
-    pub unsafe fn get_unchecked(&self, x: usize, y: usize) -> u8 {
-        self.data.as_bytes()[y * (self.width + 1)+ x]
+    /// # Safety
+    /// `x` must be less than the width and `y` less than the height.
+    pub unsafe fn get_unchecked(&self, x: usize, y: usize) -> T {
+        *self.data.get_unchecked(y * self.width + x)
     }
 
-    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+    pub fn get(&self, x: usize, y: usize) -> Option<T> {
         if x >= self.width || y >= self.height {
             return None;
         }
         Some(unsafe { self.get_unchecked(x, y) })
     }
 
-    pub fn signed_get(&self, x: isize, y: isize) -> Option<u8> {
+    pub fn signed_get(&self, x: isize, y: isize) -> Option<T> {
         if x < 0 || x as usize >= self.width
             || y < 0 || y as usize >= self.height  {
                 return None;
@@ -188,23 +97,25 @@ impl Grid {
         Some(unsafe { self.get_unchecked(x as usize, y as usize) })
     }
 
-    /// Gets the value or '\0' if out of bounds.
-    pub fn get_or_default(&self, x: usize, y: usize) -> u8 {
-        self.get(x, y).unwrap_or(0)
+    /// Gets the value or the default, '\0' for characters, if out of bounds.
+    pub fn get_or_default(&self, x: usize, y: usize) -> T where T: Default {
+        self.get(x, y).unwrap_or_default()
     }
 
-    /// Gets the value or '\0' if out of bounds.
-    pub fn signed_get_or_default(&self, x: isize, y: isize) -> u8 {
-        self.signed_get(x, y).unwrap_or(0)
+    /// Gets the value or the default, '\0' for characters, if out of bounds.
+    pub fn signed_get_or_default(&self, x: isize, y: isize) -> T where T: Default {
+        self.signed_get(x, y).unwrap_or_default()
     }
 
 
-    pub unsafe fn set_unchecked(&mut self, x: usize, y: usize, value: u8) {
-        self.data.as_bytes_mut()[y * (self.width + 1) + x] = value;
+    /// # Safety
+    /// `x` must be less than the width and `y` less than the height.
+    pub unsafe fn set_unchecked(&mut self, x: usize, y: usize, value: T) {
+        *self.data.get_unchecked_mut(y * self.width + x) = value;
     }
 
     /// Returns whether the value was set.
-    pub fn set(&mut self, x: usize, y: usize, value: u8) -> bool {
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> bool {
         if x >= self.width
             || y >= self.height  {
                 return false;
@@ -214,7 +125,7 @@ impl Grid {
     }
 
     /// Returns whether the value was set.
-    pub fn signed_set(&mut self, x: isize, y: isize, value: u8) -> bool {
+    pub fn signed_set(&mut self, x: isize, y: isize, value: T) -> bool {
         if x < 0 || x as usize >= self.width
             || y < 0 || y as usize >= self.height  {
                 return false;
@@ -224,51 +135,76 @@ impl Grid {
     }
 
 
-    /// Converts index in the data buffer itself to (x, y) coordinates.
-    fn data_index_to_xy(&self, index: usize) -> (usize, usize) {
-        let x = index % (self.width + 1);
-        let y = index / (self.width + 1);
+    /// Converts index in the grid to (x, y) coordinates.
+    fn index_to_xy(&self, index: usize) -> (usize, usize) {
+        let x = index % self.width;
+        let y = index / self.width;
         (x, y)
     }
 
-    fn data_index_to_xy_signed(&self, index: usize) -> (isize, isize) {
-        let (x, y) = self.data_index_to_xy(index);
+    fn index_to_xy_signed(&self, index: usize) -> (isize, isize) {
+        let (x, y) = self.index_to_xy(index);
         (x as isize, y as isize)
     }
 
-    fn find_index(&self, value: u8) -> Option<usize> {
+    fn find_index(&self, value: T) -> Option<usize> where T: PartialEq {
         self.data
-            .as_bytes()
             .iter()
-            .position(|byte| *byte == value)
+            .position(|cell| *cell == value)
     }
 
-    pub fn find(&self, value: u8) -> Option<(usize, usize)> {
+    pub fn find(&self, value: T) -> Option<(usize, usize)> where T: PartialEq {
         self.find_index(value)
-            .map(|index| self.data_index_to_xy(index))
+            .map(|index| self.index_to_xy(index))
     }
 
-    pub fn find_signed(&self, value: u8) -> Option<(isize, isize)> {
+    pub fn find_signed(&self, value: T) -> Option<(isize, isize)> where T: PartialEq {
         self.find_index(value)
-            .map(|index| self.data_index_to_xy_signed(index))
+            .map(|index| self.index_to_xy_signed(index))
     }
 
 
-    /// Converts index in the grid to (x, y) coordinates.
-    /// For in the grid iterators.
-    fn index_to_xy(&self, index: usize) -> (usize, usize) {
-        let x = index % self.width;
-        let y = index / self.width;
-        (x, y)
+    /// Cells in the given directions from (x, y), with their coordinates, index of their direction and value.
+    ///
+    /// The cells are read up front, so the grid can be changed while iterating.
+    fn neighbours_in<const N: usize>(&self, x: isize, y: isize, directions: [(isize, isize); N], wrap: bool) -> impl Iterator<Item = (isize, isize, usize, T)> {
+        let cells: [Option<_>; N] = array::from_fn(|dir| {
+            let (dx, dy) = directions[dir];
+            let (mut nx, mut ny) = (x + dx, y + dy);
+            if wrap {
+                nx = nx.rem_euclid(self.width.max(1) as isize);
+                ny = ny.rem_euclid(self.height.max(1) as isize);
+            }
+            Some((nx, ny, dir, self.signed_get(nx, ny)?))
+        });
+        cells.into_iter().flatten()
     }
 
-    fn index_to_xy_signed(&self, index: usize) -> (isize, isize) {
-        let (x, y) = self.index_to_xy(index);
-        (x as isize, y as isize)
+    /// Orthogonal neighbours of (x, y) inside the grid, as (x, y, direction, value),
+    /// where direction is the index in [`Directions::DIRECTIONS`].
+    pub fn neighbours(&self, x: isize, y: isize) -> impl Iterator<Item = (isize, isize, usize, T)> {
+        self.neighbours_in(x, y, isize::DIRECTIONS, false)
+    }
+
+    /// Orthogonal and diagonal neighbours of (x, y) inside the grid, as (x, y, direction, value),
+    /// where direction is the index in [`Directions::DIAGONAL_DIRECTIONS`].
+    pub fn diagonal_neighbours(&self, x: isize, y: isize) -> impl Iterator<Item = (isize, isize, usize, T)> {
+        self.neighbours_in(x, y, isize::DIAGONAL_DIRECTIONS, false)
+    }
+
+    /// Like [`neighbours`](Self::neighbours), wrapping around to the other side at the edges.
+    pub fn wrapping_neighbours(&self, x: isize, y: isize) -> impl Iterator<Item = (isize, isize, usize, T)> {
+        self.neighbours_in(x, y, isize::DIRECTIONS, true)
     }
 
+    /// Like [`diagonal_neighbours`](Self::diagonal_neighbours), wrapping around to the other side at the edges.
+    pub fn wrapping_diagonal_neighbours(&self, x: isize, y: isize) -> impl Iterator<Item = (isize, isize, usize, T)> {
+        self.neighbours_in(x, y, isize::DIAGONAL_DIRECTIONS, true)
+    }
+
+
     /// Gets a linear iterator over the grid, with coordinates included.
-    pub fn iter(&self) -> GridIterator<'_> {
+    pub fn iter(&self) -> GridIterator<'_, T> {
         GridIterator {
             grid: self,
             index: 0
@@ -276,7 +212,7 @@ impl Grid {
     }
 
     /// Gets a linear iterator over the grid, with (signed) coordinates included.
-    pub fn iter_signed(&self) -> GridIteratorSigned<'_> {
+    pub fn iter_signed(&self) -> GridIteratorSigned<'_, T> {
         GridIteratorSigned {
             grid: self,
             index: 0
@@ -284,71 +220,215 @@ impl Grid {
     }
 }
 
-impl Display for Grid {
+
+#[allow(dead_code)]
+impl Grid<u8> {
+    /// Adds a single line to the grid, should not contain newlines.
+    ///
+    /// Fails if the line has different width than the grid.
+    pub fn add_line(&mut self, line: impl AsRef<str>) -> Result<(), GridError> {
+        let line: &str = line.as_ref();
+        if line.len() != self.width {
+            return Err(GridError::DifferentWidth);
+        }
+
+        self.height += 1;
+        self.data.extend_from_slice(line.as_bytes());
+
+        Ok(())
+    }
+
+    /// Creates from iterator of lines,
+    /// using the width of the first line.
+    ///
+    /// Fails if not all lines have the same width.
+    pub fn from(mut input: impl Iterator<Item = impl AsRef<str>>) -> Result<Self, GridError> {
+        let first = input.next().unwrap();
+        let line: &str = first.as_ref();
+        let mut grid = Self {
+            width: line.len(),
+            height: 0,
+            data: Vec::with_capacity(line.len())
+        };
+
+        grid.add_line(line)?;
+        for line in input {
+            grid.add_line(line)?;
+        }
+
+        Ok(grid)
+    }
+
+    /// Create grid using the given string as body.
+    ///
+    /// Each line should be the same width,
+    /// ending newline is ignored.
+    pub fn from_string(input: String) -> Result<Self> {
+        let mut grid = Self {
+            width: input.lines().next().unwrap_or_err()?.len(),
+            height: 0,
+            data: Vec::with_capacity(input.len())
+        };
+
+        for line in input.lines() {
+            grid.add_line(line)?;
+        }
+
+        Ok(grid)
+    }
+
+    /// Adds a line to the grid, should not contain newlines.
+    ///
+    /// Fails if the line is wider than the grid, otherwise is filled to width.
+    /// Spaces by default.
+    pub fn add_line_with_fill(&mut self, line: impl AsRef<str>, fill: Option<u8>) -> Result<(), GridError> {
+        let line: &str = line.as_ref();
+        if line.len() > self.width {
+            return Err(GridError::LargerWidth);
+        }
+        let fill = fill.unwrap_or(b' ');
+
+        self.height += 1;
+        self.data.extend_from_slice(line.as_bytes());
+        self.data.resize(self.width * self.height, fill);
+
+        Ok(())
+    }
+
+    /// Creates from iterator of lines,
+    /// using the given width.
+    /// Padding with fill to reach it, spaces by default.
+    ///
+    /// Fails if any line is longer than the given width.
+    pub fn with_fill(input: impl Iterator<Item = impl AsRef<str>>, width: usize, fill: Option<u8>) -> Result<Self, GridError> {
+        let mut grid = Self {
+            width,
+            height: 0,
+            data: Vec::new()
+        };
+
+        for line in input {
+            grid.add_line_with_fill(line, fill)?;
+        }
+
+        Ok(grid)
+    }
+
+    /// Creates from iterator of lines.
+    ///
+    /// Gets the width of the widest line and pads each line with the given fill to reach it.
+    pub fn with_dynamic_width(input: impl Iterator<Item = impl AsRef<str>> + Clone, fill: Option<u8>) -> Self {
+        let mut width = 0;
+        for line in input.clone() {
+            let len = line.as_ref().len();
+            if len > width {
+                width = len;
+            }
+        }
+
+        Self::with_fill(input, width, fill)
+            .expect("Width of input changed after first iteration")
+    }
+
+    /// Converts a grid of digits to their values, `None` for cells that aren't a digit.
+    pub fn digits(&self) -> Grid<Option<u32>> {
+        self.map(|cell| char::from(cell).to_digit(10))
+    }
+}
+
+impl Display for Grid<u8> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(self.data.as_str())
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                f.write_str("\n")?;
+            }
+            f.write_str(&String::from_utf8_lossy(row))?;
+        }
+        Ok(())
     }
 }
 
-struct DebugGridContents<'a>(&'a str);
-impl<'a> Debug for DebugGridContents<'a> {
+struct DebugGridContents<'a>(&'a Grid<u8>);
+impl Debug for DebugGridContents<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("\"\"\"\n")?;
-        f.write_str(self.0)?;
+        Display::fmt(self.0, f)?;
         f.write_str("\n\"\"\"")
     }
 }
 
-impl Debug for Grid {
+impl Debug for Grid<u8> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f
             .debug_struct("Grid")
             .field("width", &self.width)
             .field("height", &self.height)
-            .field("data", &DebugGridContents(&self.data))
+            .field("data", &DebugGridContents(self))
             .finish()
     }
 }
 
 
 #[derive(Clone)]
-pub struct GridIterator<'a> {
-    grid: &'a Grid,
+pub struct GridIterator<'a, T = u8> {
+    grid: &'a Grid<T>,
     index: usize
 }
 
-impl Iterator for GridIterator<'_> {
-    type Item = (usize, usize, u8);
+impl<T: Copy> Iterator for GridIterator<'_, T> {
+    type Item = (usize, usize, T);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.grid.width * self.grid.height {
-            return None;
-        }
-
+        let value = *self.grid.data.get(self.index)?;
         let (x, y) = self.grid.index_to_xy(self.index);
         self.index += 1;
 
-        Some((x, y, self.grid.get(x, y).unwrap()))
+        Some((x, y, value))
     }
 }
 
 #[derive(Clone)]
-pub struct GridIteratorSigned<'a> {
-    grid: &'a Grid,
+pub struct GridIteratorSigned<'a, T = u8> {
+    grid: &'a Grid<T>,
     index: usize
 }
 
-impl Iterator for GridIteratorSigned<'_> {
-    type Item = (isize, isize, u8);
+impl<T: Copy> Iterator for GridIteratorSigned<'_, T> {
+    type Item = (isize, isize, T);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.grid.width * self.grid.height {
-            return None;
-        }
-
+        let value = *self.grid.data.get(self.index)?;
         let (x, y) = self.grid.index_to_xy_signed(self.index);
         self.index += 1;
 
-        Some((x, y, self.grid.signed_get(x, y).unwrap()))
+        Some((x, y, value))
+    }
+}
+
+
+#[test]
+fn test() {
+    let grid = Grid::from_string("#.#\n..#\n".to_string()).unwrap();
+    assert_eq!(grid.get_size(), (3, 2));
+    assert_eq!(grid.get(2, 1), Some(b'#'));
+    assert_eq!(grid.signed_get(-1, 0), None);
+    assert_eq!(grid.find(b'.'), Some((1, 0)));
+    assert_eq!(grid.to_string(), "#.#\n..#");
+
+    let mut counts = Grid::from_size(2, 2, 0u32);
+    for _ in 0..12 {
+        counts.set(1, 0, counts.get_or_default(1, 0) + 1);
     }
+    assert_eq!(counts.iter().collect::<Vec<_>>(), vec![(0, 0, 0), (1, 0, 12), (0, 1, 0), (1, 1, 0)]);
+
+    let digits = Grid::from(["1.", "90"].iter()).unwrap().digits();
+    assert_eq!(digits.into_vec(), vec![Some(1), None, Some(9), Some(0)]);
+    assert!(Grid::from_vec(3, vec![0; 4]).is_err());
+
+    assert_eq!(grid.neighbours(0, 0).collect::<Vec<_>>(), vec![(1, 0, 0, b'.'), (0, 1, 1, b'.')]);
+    assert_eq!(grid.diagonal_neighbours(2, 1).count(), 3);
+    assert_eq!(grid.wrapping_neighbours(0, 0).collect::<Vec<_>>(), vec![
+        (1, 0, 0, b'.'), (0, 1, 1, b'.'), (2, 0, 2, b'#'), (0, 1, 3, b'.')
+    ]);
+    assert_eq!(grid.wrapping_diagonal_neighbours(0, 0).count(), 8);
 }
@@ -1,7 +1,11 @@
 pub mod option;
+pub mod panic;
 pub mod grid;
 pub mod output;
+pub mod pathfinding;
 pub mod progress;
+pub mod region;
+pub mod toml_file;
 pub mod vector2;
 #[cfg(test)]
 pub mod test;
@@ -1,4 +1,5 @@
 use std::fmt::{self, Display};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
 
 
 #[derive(fmt::Debug, Clone)]
@@ -72,3 +73,53 @@ impl Display for OutputValue {
         }
     }
 }
+
+
+impl OutputValue {
+    /// Name of the variant, as used in machine-readable output.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::I8(_) => "I8",
+            Self::U8(_) => "U8",
+            Self::I16(_) => "I16",
+            Self::U16(_) => "U16",
+            Self::I32(_) => "I32",
+            Self::U32(_) => "U32",
+            Self::I64(_) => "I64",
+            Self::U64(_) => "U64",
+            Self::ISize(_) => "ISize",
+            Self::USize(_) => "USize",
+            Self::I128(_) => "I128",
+            Self::U128(_) => "U128",
+            Self::F32(_) => "F32",
+            Self::F64(_) => "F64",
+            Self::String(_) => "String"
+        }
+    }
+}
+
+/// Serialized as `{ "type": variant, "value": value }`.
+impl Serialize for OutputValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("OutputValue", 2)?;
+        state.serialize_field("type", self.type_name())?;
+        match self {
+            Self::I8(n) => state.serialize_field("value", n)?,
+            Self::U8(n) => state.serialize_field("value", n)?,
+            Self::I16(n) => state.serialize_field("value", n)?,
+            Self::U16(n) => state.serialize_field("value", n)?,
+            Self::I32(n) => state.serialize_field("value", n)?,
+            Self::U32(n) => state.serialize_field("value", n)?,
+            Self::I64(n) => state.serialize_field("value", n)?,
+            Self::U64(n) => state.serialize_field("value", n)?,
+            Self::ISize(n) => state.serialize_field("value", n)?,
+            Self::USize(n) => state.serialize_field("value", n)?,
+            Self::I128(n) => state.serialize_field("value", n)?,
+            Self::U128(n) => state.serialize_field("value", n)?,
+            Self::F32(n) => state.serialize_field("value", n)?,
+            Self::F64(n) => state.serialize_field("value", n)?,
+            Self::String(n) => state.serialize_field("value", n)?
+        }
+        state.end()
+    }
+}
@@ -0,0 +1,193 @@
+use std::{any::Any, backtrace::Backtrace, cell::Cell, error::Error, fmt::{self, Display}, panic::{self, AssertUnwindSafe}, sync::{atomic::{AtomicU64, Ordering}, Mutex, MutexGuard, Once}};
+use color_eyre::{eyre::{Report, Result}, Section, SectionExt};
+
+
+thread_local! {
+    /// Token of the [`catch_panic`] running on this thread, so the hook knows which run a panic belongs to.
+    static RUN: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+static NEXT_RUN: AtomicU64 = AtomicU64::new(0);
+/// Panics remembered by the hook, until the run they belong to takes them.
+static CAUGHT: Mutex<Vec<Caught>> = Mutex::new(Vec::new());
+/// Most panics on rayon threads kept for a run to take, older ones are forgotten.
+const MAX_UNCLAIMED: usize = 32;
+
+static INSTALL_HOOK: Once = Once::new();
+
+
+/// A panic remembered by the hook.
+///
+/// Rayon threads may run work of any run, even while they wait inside a [`catch_panic`] of their own.
+/// The run that rayon hands a panic to recognises it by the address of its message, which moves along with the payload.
+struct Caught {
+    /// Run of the thread that panicked, if it was catching.
+    run: Option<u64>,
+    address: Option<usize>,
+    panic: SolverPanic,
+    backtrace: String
+}
+
+fn caught() -> MutexGuard<'static, Vec<Caught>> {
+    // the hook never panics while holding the lock, so a poisoned one is still consistent
+    CAUGHT.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Address of the message of a panic, the same in the hook and after catching it.
+fn payload_address(payload: &(dyn Any + Send)) -> Option<usize> {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))?;
+    // empty messages all share a dangling address
+    (!message.is_empty()).then_some(message.as_ptr() as usize)
+}
+
+
+/// A panic of a solver, turned into an error.
+#[derive(Debug, Clone)]
+pub struct SolverPanic {
+    pub message: String,
+    /// `file:line:column` of the panic, unknown when it didn't go through the panic hook.
+    pub location: Option<String>
+}
+
+impl Display for SolverPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "The solver panicked at {location}: {}", self.message),
+            None => write!(f, "The solver panicked: {}", self.message)
+        }
+    }
+}
+
+impl Error for SolverPanic {}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Wraps the panic hook, so panics of a run that is catching them are remembered instead of printed.
+///
+/// Panics on rayon threads outside a run are remembered and printed, as they may not reach a run that catches them.
+fn install_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let run = RUN.get();
+        let address = payload_address(info.payload());
+        let unclaimed = run.is_none();
+        if unclaimed && (address.is_none() || rayon::current_thread_index().is_none()) {
+            return previous(info);
+        }
+
+        let panic = SolverPanic {
+            message: payload_message(info.payload()),
+            location: info.location().map(|location| location.to_string())
+        };
+        let backtrace = Backtrace::force_capture().to_string();
+        let mut caught = caught();
+        caught.push(Caught { run, address, panic, backtrace });
+        if unclaimed {
+            if caught.iter().filter(|caught| caught.run.is_none()).count() > MAX_UNCLAIMED {
+                let oldest = caught.iter().position(|caught| caught.run.is_none());
+                caught.remove(oldest.expect("Counted an unclaimed panic"));
+            }
+            drop(caught);
+            previous(info);
+        }
+    }));
+}
+
+/// Takes the panic with the payload's message, preferring one of `run` and else taking the newest one of `run`,
+/// forgetting the other panics of the run.
+fn take_panic(run: u64, payload: Option<&(dyn Any + Send)>) -> Option<(SolverPanic, String)> {
+    let mut caught = caught();
+    let address = payload.and_then(payload_address);
+    let same_payload = |caught: &Caught| address.is_some() && caught.address == address;
+    let index = caught
+        .iter()
+        .rposition(|caught| caught.run == Some(run) && same_payload(caught))
+        .or_else(|| caught.iter().rposition(same_payload))
+        .or_else(|| caught.iter().rposition(|caught| caught.run == Some(run)));
+    let taken = index.map(|index| caught.remove(index));
+    caught.retain(|caught| caught.run != Some(run));
+    taken
+        .filter(|_| payload.is_some())
+        .map(|taken| (taken.panic, taken.backtrace))
+}
+
+/// Runs `f`, turning a panic into a [`SolverPanic`] error with the backtrace of the panic as a section,
+/// also when it happened on another thread, like those of rayon.
+pub fn catch_panic<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    INSTALL_HOOK.call_once(install_hook);
+
+    let run = NEXT_RUN.fetch_add(1, Ordering::Relaxed);
+    let outer = RUN.replace(Some(run));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    RUN.set(outer);
+    let panic = take_panic(run, result.as_ref().err().map(|payload| payload.as_ref()));
+
+    result.unwrap_or_else(|payload| {
+        let report = match panic {
+            Some((panic, backtrace)) => Report::new(panic).section(backtrace.header("Panic backtrace:")),
+            // panics that didn't reach the hook, like those resumed with their payload
+            None => Report::new(SolverPanic {
+                message: payload_message(payload.as_ref()),
+                location: None
+            })
+        };
+        Err(report)
+    })
+}
+
+
+#[test]
+fn test() {
+    use std::{sync::mpsc, thread};
+    use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+    let err = catch_panic::<()>(|| panic!("bad input")).unwrap_err();
+    let panic = err.downcast_ref::<SolverPanic>().unwrap();
+    assert_eq!(panic.message, "bad input");
+    assert!(panic.location.as_ref().unwrap().starts_with("src/misc/panic.rs:"));
+
+    assert_eq!(catch_panic(|| Ok(5)).unwrap(), 5);
+
+    let err = catch_panic::<()>(|| {
+        (0..64).into_par_iter().for_each(|i| assert_ne!(i, 40, "bad item"));
+        Ok(())
+    }).unwrap_err();
+    let panic = err.downcast_ref::<SolverPanic>().unwrap();
+    assert!(panic.message.contains("bad item"));
+    assert!(panic.location.as_ref().unwrap().starts_with("src/misc/panic.rs:"));
+    assert!(format!("{err:?}").contains("Panic backtrace:"));
+
+    let formatted = catch_panic::<()>(|| {
+        (0..64).into_par_iter().for_each(|i| assert!(i != 20, "bad item {}", i / 10));
+        Ok(())
+    }).unwrap_err();
+    let panic = formatted.downcast_ref::<SolverPanic>().unwrap();
+    assert_eq!(panic.message, "bad item 2");
+    assert!(panic.location.as_ref().unwrap().starts_with("src/misc/panic.rs:"));
+
+    // a run still going, like one that timed out, keeps its panics to itself
+    let (panicked, has_panicked) = mpsc::channel();
+    let (sender, receiver) = mpsc::channel();
+    let running = thread::spawn(move || catch_panic(|| {
+        let _ = panic::catch_unwind(|| panic!("same"));
+        panicked.send(()).map_err(Report::new)?;
+        receiver.recv().map_err(Report::new)
+    }));
+    has_panicked.recv().unwrap();
+    let (err, line) = (catch_panic::<()>(|| panic!("same")).unwrap_err(), line!());
+    let panic = err.downcast_ref::<SolverPanic>().unwrap();
+    assert!(panic.location.as_ref().unwrap().starts_with(&format!("src/misc/panic.rs:{line}:")));
+    sender.send(()).unwrap();
+    running.join().unwrap().unwrap();
+}
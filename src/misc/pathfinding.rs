@@ -0,0 +1,296 @@
+use std::{cmp::Reverse, collections::{HashMap, HashSet, VecDeque}, hash::Hash};
+use priority_queue::PriorityQueue;
+use super::{grid::Grid, vector2::Directions};
+
+
+/// Position in a [`Grid`].
+pub type Pos = (isize, isize);
+/// Position in a [`Grid`] and the index of the direction it faces in [`Directions::DIRECTIONS`].
+pub type Facing = (Pos, usize);
+
+
+/// Result of a search, with the shortest distance to every node that was reached
+/// and all nodes each of them can be reached from at that distance.
+#[derive(Debug, Clone)]
+pub struct Paths<N> {
+    pub distances: HashMap<N, usize>,
+    predecessors: HashMap<N, Vec<N>>,
+    /// Ends reached at the shortest distance, empty when none were reached.
+    pub ends: Vec<N>
+}
+
+impl<N: Clone + Hash + Eq> Paths<N> {
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            ends: Vec::new()
+        }
+    }
+
+    /// Records reaching `next` from `current` at `distance`, returning whether it is closer than before.
+    fn relax(&mut self, current: &N, next: N, distance: usize) -> bool {
+        match self.distances.get(&next) {
+            Some(old) if *old < distance => false,
+            Some(old) if *old == distance => {
+                // starts have no entry, they stay starts even when a step costing 0 leads back to them
+                if let Some(predecessors) = self.predecessors.get_mut(&next) {
+                    predecessors.push(current.clone());
+                }
+                false
+            }
+            _ => {
+                self.distances.insert(next.clone(), distance);
+                self.predecessors.insert(next, vec![current.clone()]);
+                true
+            }
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<usize> {
+        self.distances.get(node).copied()
+    }
+
+    /// Distance to the closest end.
+    pub fn end_distance(&self) -> Option<usize> {
+        self.distance(self.ends.first()?)
+    }
+
+    /// Nodes `node` is reached from on its shortest paths, empty for starts.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors
+            .get(node)
+            .map_or(&[], |predecessors| predecessors.as_slice())
+    }
+
+    /// A shortest path from a start to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.distances.get(node)?;
+
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors(path.last()?).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// All nodes on any shortest path to one of `targets`, including the starts and targets.
+    pub fn nodes_on_paths(&self, targets: impl IntoIterator<Item = N>) -> HashSet<N> {
+        let mut nodes = HashSet::new();
+        let mut stack = targets
+            .into_iter()
+            .filter(|target| self.distances.contains_key(target))
+            .collect::<Vec<_>>();
+
+        while let Some(node) = stack.pop() {
+            if nodes.contains(&node) {
+                continue;
+            }
+            stack.extend(self.predecessors(&node).iter().cloned());
+            nodes.insert(node);
+        }
+        nodes
+    }
+
+    /// Number of different shortest paths from the starts to `node`.
+    ///
+    /// Cycles of steps that cost 0 are only followed once, rather than counting infinitely many paths.
+    pub fn path_count(&self, node: &N) -> usize {
+        if !self.distances.contains_key(node) {
+            return 0;
+        }
+
+        let mut counts = HashMap::<N, usize>::new();
+        let mut visiting = HashSet::new();
+        let mut stack = vec![node.clone()];
+        while let Some(current) = stack.last().cloned() {
+            if counts.contains_key(&current) {
+                stack.pop();
+                continue;
+            }
+            visiting.insert(current.clone());
+
+            let predecessors = self.predecessors(&current);
+            let pending = predecessors
+                .iter()
+                .filter(|previous| !counts.contains_key(previous) && !visiting.contains(previous))
+                .cloned()
+                .collect::<Vec<_>>();
+            if !pending.is_empty() {
+                stack.extend(pending);
+                continue;
+            }
+
+            let count = match predecessors.is_empty() {
+                true => 1,
+                false => predecessors
+                    .iter()
+                    .map(|previous| counts.get(previous).copied().unwrap_or(0))
+                    .sum()
+            };
+            visiting.remove(&current);
+            counts.insert(current, count);
+            stack.pop();
+        }
+        counts[node]
+    }
+}
+
+
+/// Breadth first search where every step costs 1.
+///
+/// Stops once all ends at the shortest distance are found, or searches everything when `is_end` is never true.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_end: impl FnMut(&N) -> bool
+) -> Paths<N>
+where
+    N: Clone + Hash + Eq,
+    I: IntoIterator<Item = N>
+{
+    let mut paths = Paths::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        paths.distances.insert(start.clone(), 0);
+        queue.push_back(start);
+    }
+
+    while let Some(current) = queue.pop_front() {
+        let distance = paths.distances[&current];
+        if paths.end_distance().is_some_and(|end| distance > end) {
+            break;
+        }
+        if is_end(&current) {
+            paths.ends.push(current);
+            continue;
+        }
+
+        for next in successors(&current) {
+            if paths.relax(&current, next.clone(), distance + 1) {
+                queue.push_back(next);
+            }
+        }
+    }
+    paths
+}
+
+/// Dijkstra's search where `successors` gives the next nodes with the cost of getting there.
+///
+/// Stops once all ends at the shortest distance are found, or searches everything when `is_end` is never true.
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_end: impl FnMut(&N) -> bool
+) -> Paths<N>
+where
+    N: Clone + Hash + Eq,
+    I: IntoIterator<Item = (N, usize)>
+{
+    astar(starts, successors, |_| 0, is_end)
+}
+
+/// A* search, like [`dijkstra`] but trying nodes with the lowest distance plus `heuristic` first.
+///
+/// The heuristic must never overestimate the distance to an end, and should be 0 at the ends.
+pub fn astar<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> usize,
+    mut is_end: impl FnMut(&N) -> bool
+) -> Paths<N>
+where
+    N: Clone + Hash + Eq,
+    I: IntoIterator<Item = (N, usize)>
+{
+    let mut paths = Paths::new();
+    let mut queue = PriorityQueue::new();
+    for start in starts {
+        paths.distances.insert(start.clone(), 0);
+        let estimate = heuristic(&start);
+        queue.push(start, Reverse(estimate));
+    }
+
+    while let Some((current, Reverse(estimate))) = queue.pop() {
+        if paths.end_distance().is_some_and(|end| estimate > end) {
+            break;
+        }
+        if is_end(&current) {
+            paths.ends.push(current);
+            continue;
+        }
+
+        let distance = paths.distances[&current];
+        for (next, cost) in successors(&current) {
+            if paths.relax(&current, next.clone(), distance + cost) {
+                let estimate = distance + cost + heuristic(&next);
+                queue.push_increase(next, Reverse(estimate));
+            }
+        }
+    }
+    paths
+}
+
+
+/// Neighbours of `pos` that aren't walls, for searching a [`Grid`] with [`bfs`].
+pub fn open_neighbours<T: Copy>(grid: &Grid<T>, (x, y): Pos, is_wall: impl Fn(T) -> bool) -> impl Iterator<Item = Pos> {
+    grid.neighbours(x, y)
+        .filter(move |(_, _, _, cell)| !is_wall(*cell))
+        .map(|(nx, ny, _, _)| (nx, ny))
+}
+
+/// Steps to neighbours of `pos` that aren't walls, ending up facing the direction of the step,
+/// costing 1 plus `turn_cost` for every quarter turn. For searching a [`Grid`] with [`dijkstra`].
+pub fn turning_neighbours<T: Copy>(
+    grid: &Grid<T>,
+    ((x, y), facing): Facing,
+    is_wall: impl Fn(T) -> bool,
+    turn_cost: usize
+) -> impl Iterator<Item = (Facing, usize)> {
+    let turns = isize::DIRECTIONS.len();
+    grid.neighbours(x, y)
+        .filter(move |(_, _, _, cell)| !is_wall(*cell))
+        .map(move |(nx, ny, dir, _)| {
+            let rot = (dir + turns - facing) % turns;
+            (((nx, ny), dir), 1 + turn_cost * rot.min(turns - rot))
+        })
+}
+
+
+#[test]
+fn test() {
+    let grid = Grid::from_string("...\n.#.\n...".to_string()).unwrap();
+    let wall = |cell| cell == b'#';
+
+    let paths = bfs([(0, 0)], |pos| open_neighbours(&grid, *pos, wall), |pos| *pos == (2, 2));
+    assert_eq!(paths.end_distance(), Some(4));
+    assert_eq!(paths.path_to(&(2, 2)).unwrap().len(), 5);
+    assert_eq!(paths.path_count(&(2, 2)), 2);
+    assert_eq!(paths.nodes_on_paths([(2, 2)]).len(), 8);
+
+    let all = bfs([(0, 0)], |pos| open_neighbours(&grid, *pos, wall), |_| false);
+    assert_eq!(all.distances.len(), 8);
+    assert_eq!(all.predecessors(&(0, 0)), &[]);
+
+    let turning = dijkstra([((0, 0), 0)], |state| turning_neighbours(&grid, *state, wall, 10), |(pos, _)| *pos == (0, 2));
+    assert_eq!(turning.end_distance(), Some(12));
+
+    let manhattan = |(x, y): &Pos| x.abs_diff(2) + y.abs_diff(2);
+    let guided = astar([(0, 0)], |pos| open_neighbours(&grid, *pos, wall).map(|pos| (pos, 1)), manhattan, |pos| *pos == (2, 2));
+    assert_eq!(guided.end_distance(), Some(4));
+    assert_eq!(guided.path_count(&(2, 2)), 2);
+
+    // 1 and 3 are as far as 0, after steps that cost nothing
+    let edges = [(0, 1, 0), (0, 2, 1), (1, 2, 1), (1, 0, 0), (1, 3, 0), (3, 1, 0)];
+    let successors = |node: &i32| edges
+        .iter()
+        .filter(|(from, _, _)| from == node)
+        .map(|(_, to, cost)| (*to, *cost))
+        .collect::<Vec<_>>();
+    let free = dijkstra([0], successors, |_| false);
+    assert_eq!(free.distance(&1), Some(0));
+    assert_eq!(free.path_count(&2), 2);
+    assert_eq!(free.path_count(&3), 1);
+    assert_eq!(free.path_count(&4), 0);
+}
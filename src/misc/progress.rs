@@ -1,19 +1,65 @@
-use std::time::Duration;
-use indicatif::{ProgressBar, ProgressStyle};
+use std::{cell::RefCell, sync::{Arc, Mutex}, time::Duration};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use lazy_static::lazy_static;
 
 
 lazy_static!{
     static ref PROGRESS_STYLE: ProgressStyle =
-        ProgressStyle::with_template("[{elapsed_precise}] {spinner} {bar:64} {pos:>4}/{len:4} {eta} {msg}")
+        ProgressStyle::with_template("{prefix}[{elapsed_precise}] {spinner} {bar:64} {pos:>4}/{len:4} {eta} {msg}")
             .expect("Progress style is invalid")
             .progress_chars("#<-")
             .tick_chars(r"-\|/.");
+
+    /// Every progress bar is drawn as its own line of this, so days running at the same time don't draw over each other.
+    pub static ref MULTI_PROGRESS: MultiProgress = MultiProgress::new();
+}
+
+thread_local! {
+    /// Scope the progress bars created on this thread belong to.
+    static SCOPE: RefCell<ProgressScope> = RefCell::new(ProgressScope::default());
+}
+
+
+/// The progress bars of a single day, prefixed by a label naming it.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressScope {
+    label: String,
+    bars: Arc<Mutex<Vec<ProgressBar>>>
 }
 
+impl ProgressScope {
+    pub fn new(label: String) -> Self {
+        Self {
+            label,
+            bars: Arc::default()
+        }
+    }
+
+    /// Runs `f` with the progress bars it creates on this thread belonging to this scope.
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        // another day may run on this thread while this one waits for its own parallel work, so restore its scope after
+        let previous = SCOPE.replace(self.clone());
+        let result = f();
+        SCOPE.set(previous);
+        result
+    }
+
+    /// Progress bars created in this scope so far, oldest first.
+    pub fn bars(&self) -> Vec<ProgressBar> {
+        self.bars.lock().expect("Progress bars are poisoned").clone()
+    }
+}
+
+
 pub fn pretty_progress_bar(length: u64) -> ProgressBar {
-    let bar = ProgressBar::new(length);
+    let bar = MULTI_PROGRESS.add(ProgressBar::new(length));
     bar.set_style(PROGRESS_STYLE.clone());
+    SCOPE.with_borrow(|scope| {
+        if !scope.label.is_empty() {
+            bar.set_prefix(format!("{} ", scope.label));
+        }
+        scope.bars.lock().expect("Progress bars are poisoned").push(bar.clone());
+    });
     bar.enable_steady_tick(Duration::from_millis(150));
 
     bar
@@ -0,0 +1,130 @@
+use std::collections::{HashSet, VecDeque};
+use super::{grid::Grid, vector2::Directions};
+
+
+/// Connected cells of a [`Grid`], found with [`Grid::regions`] or [`Grid::regions_where`].
+#[derive(Debug, Clone)]
+pub struct Region<T = u8> {
+    /// Value of the first cell of the region.
+    pub value: T,
+    /// Cells in the order they were found, starting from the top left one.
+    pub cells: Vec<(isize, isize)>,
+    members: HashSet<(isize, isize)>
+}
+
+impl<T> Region<T> {
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        self.members.contains(&(x, y))
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of cell edges between the region and the cells around it.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|(x, y)| isize::DIRECTIONS
+                .iter()
+                .filter(|(dx, dy)| !self.contains(x + dx, y + dy))
+                .count())
+            .sum()
+    }
+
+    /// Number of straight sides of the outline, including those of holes, which equals the number of corners.
+    pub fn sides(&self) -> usize {
+        let directions = isize::DIRECTIONS;
+        let mut corners = 0;
+        for (x, y) in &self.cells {
+            for (dir, (dx, dy)) in directions.iter().enumerate() {
+                let (dx2, dy2) = directions[(dir + 1) % directions.len()];
+                let side = self.contains(x + dx, y + dy);
+                let other_side = self.contains(x + dx2, y + dy2);
+                let diagonal = self.contains(x + dx + dx2, y + dy + dy2);
+
+                // outer corners stick out, inner corners are the cell's part of a bend
+                if (!side && !other_side) || (side && other_side && !diagonal) {
+                    corners += 1;
+                }
+            }
+        }
+        corners
+    }
+
+    /// Top left and bottom right cell of the smallest rectangle around the region.
+    pub fn bounding_box(&self) -> ((isize, isize), (isize, isize)) {
+        self.cells.iter().fold(
+            ((isize::MAX, isize::MAX), (isize::MIN, isize::MIN)),
+            |((min_x, min_y), (max_x, max_y)), (x, y)| (
+                (min_x.min(*x), min_y.min(*y)),
+                (max_x.max(*x), max_y.max(*y))
+            )
+        )
+    }
+}
+
+
+impl<T: Copy> Grid<T> {
+    /// Flood fills every cell that is `included`, joining orthogonal neighbours that are `connected`.
+    fn regions_by(&self, included: impl Fn(T) -> bool, connected: impl Fn(T, T) -> bool) -> Vec<Region<T>> {
+        let mut found = HashSet::new();
+        let mut regions = Vec::new();
+
+        for (x, y, value) in self.iter_signed() {
+            if !included(value) || found.contains(&(x, y)) {
+                continue;
+            }
+            found.insert((x, y));
+
+            let mut cells = vec![(x, y)];
+            let mut queue = VecDeque::from([(x, y, value)]);
+            while let Some((x, y, current)) = queue.pop_front() {
+                for (nx, ny, _, next) in self.neighbours(x, y) {
+                    if !included(next) || !connected(current, next) || found.contains(&(nx, ny)) {
+                        continue;
+                    }
+                    found.insert((nx, ny));
+                    cells.push((nx, ny));
+                    queue.push_back((nx, ny, next));
+                }
+            }
+
+            regions.push(Region {
+                value,
+                members: cells.iter().copied().collect(),
+                cells
+            });
+        }
+        regions
+    }
+
+    /// Regions of neighbouring cells with the same value, covering the whole grid.
+    pub fn regions(&self) -> Vec<Region<T>> where T: PartialEq {
+        self.regions_by(|_| true, |a, b| a == b)
+    }
+
+    /// Regions of neighbouring cells matching `predicate`, ignoring the other cells.
+    pub fn regions_where(&self, predicate: impl Fn(T) -> bool) -> Vec<Region<T>> {
+        self.regions_by(predicate, |_, _| true)
+    }
+}
+
+
+#[test]
+fn test() {
+    let grid = Grid::from_string("AAAA\nBBCD\nBBCC\nEEEC\n".to_string()).unwrap();
+    let regions = grid.regions();
+    assert_eq!(regions.len(), 5);
+    assert_eq!(regions.iter().map(|region| region.area() * region.perimeter()).sum::<usize>(), 140);
+    assert_eq!(regions.iter().map(|region| region.area() * region.sides()).sum::<usize>(), 80);
+
+    let c = regions.iter().find(|region| region.value == b'C').unwrap();
+    assert_eq!(c.bounding_box(), ((2, 1), (3, 3)));
+    assert!(c.contains(3, 2) && !c.contains(3, 1));
+
+    let ring = Grid::from_string("###\n#.#\n###\n".to_string()).unwrap();
+    let walls = ring.regions_where(|cell| cell == b'#');
+    assert_eq!(walls.len(), 1);
+    assert_eq!((walls[0].area(), walls[0].perimeter(), walls[0].sides()), (8, 16, 8));
+}
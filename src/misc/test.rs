@@ -1,6 +1,7 @@
-use std::io::{BufReader, Cursor, Read};
+use std::{env, fs, io::{BufRead, BufReader, Cursor, Read, Write}, net::TcpListener, path::{Path, PathBuf}, process, sync::{Arc, Mutex}, thread};
+use color_eyre::eyre::Result;
 use ctor::ctor;
-use crate::{Input, Output};
+use crate::{examples, params::Params, runner::RunOutcome, solver::{self, Part}, Input, Output, PartOutput};
 
 
 #[ctor]
@@ -11,12 +12,33 @@ fn test_setup() {
 pub fn str_to_input(input: &str) -> Input {
     let cursor = Cursor::new(input.to_owned());
 
-    BufReader::new(Box::new(cursor) as Box<dyn Read>)
+    BufReader::new(Box::new(cursor) as Box<dyn Read + Send>)
 }
 
-pub fn test_solver(solver: fn(Input) -> Output, input: &str, expected: Output) {
-    let input = str_to_input(input);
-    let result = solver(input);
+/// A solve or parse function of a day, taking [`Params`] or not like in [`register_day!`](crate::register_day).
+///
+/// `Marker` tells the two apart, it is inferred from the function.
+trait DayFn<Marker, R> {
+    fn call(&self, input: Input, params: &Params) -> R;
+}
+
+struct WithoutParams;
+struct WithParams;
+
+impl<F: Fn(Input) -> R, R> DayFn<WithoutParams, R> for F {
+    fn call(&self, input: Input, _: &Params) -> R {
+        self(input)
+    }
+}
+
+impl<F: Fn(Input, &Params) -> R, R> DayFn<WithParams, R> for F {
+    fn call(&self, input: Input, params: &Params) -> R {
+        self(input, params)
+    }
+}
+
+fn check_solver<M>(solver: impl DayFn<M, Output>, params: &Params, input: &str, expected: Output) {
+    let result = solver.call(str_to_input(input), params);
 
     match expected {
         Err(_) => assert!(result.is_err(), "Expected error, but got {result:?}"),
@@ -28,3 +50,125 @@ pub fn test_solver(solver: fn(Input) -> Output, input: &str, expected: Output) {
         }
     }
 }
+
+fn check_part<M, T>(parse: impl DayFn<M, Result<T>>, params: &Params, part: fn(&T) -> PartOutput, input: &str, expected: PartOutput) {
+    let result = parse.call(str_to_input(input), params).and_then(|parsed| part(&parsed));
+
+    match expected {
+        Err(_) => assert!(result.is_err(), "Expected error, but got {result:?}"),
+        Ok(expected) => assert_eq!(result.unwrap().to_string(), expected.to_string())
+    }
+}
+
+pub fn test_solver(solver: fn(Input) -> Output, input: &str, expected: Output) {
+    check_solver(solver, &Params::default(), input, expected);
+}
+
+/// Tests a solver taking parameters, with the parameter values of the example.
+pub fn test_solver_with(solver: fn(Input, &Params) -> Output, params: Params, input: &str, expected: Output) {
+    check_solver(solver, &params, input, expected);
+}
+
+/// Tests a single part of a solver that parses its input separately.
+pub fn test_part<T>(parse: fn(Input) -> Result<T>, part: fn(&T) -> PartOutput, input: &str, expected: PartOutput) {
+    check_part(parse, &Params::default(), part, input, expected);
+}
+
+/// Tests a single part of a solver whose parsing takes parameters, with the parameter values of the example.
+pub fn test_part_with<T>(parse: fn(Input, &Params) -> Result<T>, params: &Params, part: fn(&T) -> PartOutput, input: &str, expected: PartOutput) {
+    check_part(parse, params, part, input, expected);
+}
+
+/// Tests a registered day with each of its examples in the fixtures directory,
+/// checking the parts that have an expected answer.
+pub fn test_examples(year: i32, day: u32) {
+    let solver = solver::find(year, day).expect("Day is not registered");
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+    let examples = examples::load(&fixtures, year, day).unwrap();
+    assert!(!examples.is_empty(), "Day has no examples");
+
+    for example in examples {
+        let run = example.run(solver, &Part::ALL).unwrap();
+        let RunOutcome::Solved(parts) = run.outcome else {
+            panic!("Example {} failed", example.name);
+        };
+
+        for part in parts {
+            let Some(expected) = example.expected(part.part) else {
+                continue;
+            };
+            assert_eq!(part.result.unwrap().to_string(), expected, "Example {} part {}", example.name, part.part);
+        }
+    }
+}
+
+/// A request received by [`mock_server`].
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    /// Request line, e.g. `GET /2024/day/1/input HTTP/1.1`.
+    pub line: String,
+    pub headers: Vec<String>,
+    pub body: String
+}
+
+/// Serves HTTP on a random local port, answering each request with the status and body
+/// returned by `respond`.
+///
+/// Returns the base url of the server and the requests it received so far.
+pub fn mock_server(respond: impl Fn(&MockRequest) -> (u16, String) + Send + 'static) -> (String, Arc<Mutex<Vec<MockRequest>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Could not bind mock server");
+    let url = format!("http://{}", listener.local_addr().expect("Mock server has no address"));
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let received = requests.clone();
+    thread::spawn(move || for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let mut reader = BufReader::new(stream);
+
+        let mut line = String::new();
+        if reader.read_line(&mut line).is_err() {
+            continue;
+        }
+        let mut headers = Vec::new();
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header).is_err() || header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap_or(0);
+                }
+            }
+            headers.push(header.trim().to_string());
+        }
+        let mut body = vec![0; length];
+        if reader.read_exact(&mut body).is_err() {
+            continue;
+        }
+
+        let request = MockRequest {
+            line: line.trim().to_string(),
+            headers,
+            body: String::from_utf8_lossy(&body).to_string()
+        };
+        let (status, body) = respond(&request);
+        received.lock().expect("Mock server requests are poisoned").push(request);
+
+        let response = format!("HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len());
+        let _ = reader.get_mut().write_all(response.as_bytes());
+    });
+
+    (url, requests)
+}
+
+/// New empty directory in the temporary directory, unique to the calling test.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-test-{name}-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("Could not create temporary directory");
+    dir
+}
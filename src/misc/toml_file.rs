@@ -0,0 +1,37 @@
+use std::{fs, io::ErrorKind, path::Path};
+use color_eyre::eyre::{Result, WrapErr};
+use serde::de::DeserializeOwned;
+
+
+/// Reads and parses a TOML file, the default value if it doesn't exist yet.
+///
+/// `what` names the file in errors, like `answers file`.
+pub fn load_or_default<T: DeserializeOwned + Default>(path: &Path, what: &str) -> Result<T> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(T::default()),
+        Err(err) => return Err(err).wrap_err_with(|| format!("Could not read {}", path.display()))
+    };
+
+    toml::from_str(&text).wrap_err_with(|| format!("Invalid {what} {}", path.display()))
+}
+
+
+#[test]
+fn test() {
+    use std::collections::BTreeMap;
+    use super::test::temp_dir;
+
+    let dir = temp_dir("toml_file");
+    let path = dir.join("file.toml");
+    assert!(load_or_default::<BTreeMap<String, u32>>(&path, "file").unwrap().is_empty());
+
+    fs::write(&path, "a = 1\n").unwrap();
+    assert_eq!(load_or_default::<BTreeMap<String, u32>>(&path, "file").unwrap()["a"], 1);
+
+    fs::write(&path, "a = \"one\"\n").unwrap();
+    let err = load_or_default::<BTreeMap<String, u32>>(&path, "file").unwrap_err();
+    assert!(err.to_string().starts_with("Invalid file "));
+    assert!(load_or_default::<BTreeMap<String, u32>>(&dir, "file").is_err(), "A directory can't be read");
+    fs::remove_dir_all(dir).unwrap();
+}
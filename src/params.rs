@@ -0,0 +1,109 @@
+use std::{collections::HashMap, error::Error, fmt::{self, Display}, str::FromStr};
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+
+
+/// A value a solver depends on that differs between the examples and the real puzzle,
+/// like the size of a grid.
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    /// Value used for the real puzzle.
+    pub default: &'static str,
+    pub help: &'static str
+}
+
+impl Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.default)
+    }
+}
+
+
+/// Values of the parameters of a solver, the defaults unless overridden.
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    values: HashMap<&'static str, String>
+}
+
+impl Params {
+    pub fn defaults(declared: &[Param]) -> Self {
+        Self {
+            values: declared
+                .iter()
+                .map(|param| (param.name, param.default.to_string()))
+                .collect()
+        }
+    }
+
+    /// Defaults of the declared parameters, with the given values overriding them.
+    /// Fails if a value is given for an undeclared parameter, or more than once for the same one.
+    pub fn new(declared: &[Param], overrides: &[(impl AsRef<str>, impl AsRef<str>)]) -> Result<Self> {
+        let mut params = Self::defaults(declared);
+        let mut overridden = Vec::new();
+
+        for (name, value) in overrides {
+            let Some(param) = declared.iter().find(|param| param.name == name.as_ref()) else {
+                let names = declared.iter().map(|param| param.name).collect::<Vec<_>>();
+                if names.is_empty() {
+                    bail!("Unknown parameter {}, this day has no parameters", name.as_ref());
+                }
+                bail!("Unknown parameter {}, this day has {}", name.as_ref(), names.join(", "));
+            };
+            if overridden.contains(&param.name) {
+                bail!("Parameter {} is given more than once", param.name);
+            }
+            overridden.push(param.name);
+            params.values.insert(param.name, value.as_ref().to_string());
+        }
+
+        Ok(params)
+    }
+
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Error + Send + Sync + 'static
+    {
+        let value = self.values
+            .get(name)
+            .ok_or_else(|| eyre!("Parameter {name} is not declared"))?;
+        value
+            .parse()
+            .wrap_err_with(|| format!("Invalid value {value:?} for parameter {name}"))
+    }
+}
+
+
+/// Parses a `key=value` command line argument.
+pub fn parse_override(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, value)) => Ok((name.trim().to_string(), value.trim().to_string())),
+        None => Err(format!("expected key=value, got {arg:?}"))
+    }
+}
+
+
+#[test]
+fn test() {
+    const DECLARED: &[Param] = &[
+        Param { name: "size", default: "70", help: "" },
+        Param { name: "name", default: "abc", help: "" }
+    ];
+
+    let defaults = Params::new(DECLARED, &[] as &[(&str, &str)]).unwrap();
+    assert_eq!(defaults.get::<usize>("size").unwrap(), 70);
+    assert_eq!(defaults.get::<String>("name").unwrap(), "abc");
+
+    let params = Params::new(DECLARED, &[("size", "6")]).unwrap();
+    assert_eq!(params.get::<usize>("size").unwrap(), 6);
+    assert!(params.get::<usize>("other").is_err());
+
+    let unknown = Params::new(DECLARED, &[("other", "1")]).unwrap_err();
+    assert_eq!(unknown.to_string(), "Unknown parameter other, this day has size, name");
+    assert!(Params::new(&[], &[("size", "1")]).is_err());
+    assert!(Params::new(DECLARED, &[("size", "6"), ("size", "7")]).is_err());
+    assert!(Params::new(DECLARED, &[("size", "-1")]).unwrap().get::<usize>("size").is_err());
+
+    assert_eq!(parse_override(" size = 6 ").unwrap(), ("size".to_string(), "6".to_string()));
+    assert!(parse_override("size").is_err());
+}
@@ -0,0 +1,227 @@
+use std::time::Duration;
+use clap::ValueEnum;
+use color_eyre::eyre::Report;
+use serde::Serialize;
+use crate::{memory::MemoryStats, misc::{output::OutputValue, panic::SolverPanic}, runner::{DayRun, RunOutcome}};
+
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Human readable text
+    #[default]
+    Text,
+    /// A JSON object per day, an array of them when running multiple days
+    Json
+}
+
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Parsing the input, or solving a day that solves both parts at once, failed.
+    Parse,
+    /// Solving a single part failed.
+    Part,
+    /// The input doesn't exist or could not be opened.
+    MissingInput,
+    TimedOut,
+    NoSolution,
+    /// The solver panicked while parsing or solving.
+    Panic,
+    /// The year or day doesn't exist or hasn't unlocked yet.
+    UnknownPuzzle,
+    /// A `--param` doesn't exist or was given more than once.
+    InvalidParams
+}
+
+#[derive(Serialize)]
+pub struct ErrorReport {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// Messages of the errors that caused this one, outermost first.
+    pub causes: Vec<String>
+}
+
+impl ErrorReport {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            causes: Vec::new()
+        }
+    }
+
+    /// Error of a report, of the [`Panic`](ErrorKind::Panic) kind instead when the solver panicked.
+    pub fn from_report(kind: ErrorKind, report: &Report) -> Self {
+        let kind = match report.downcast_ref::<SolverPanic>() {
+            Some(_) => ErrorKind::Panic,
+            None => kind
+        };
+        Self {
+            kind,
+            message: report.to_string(),
+            causes: report
+                .chain()
+                .skip(1)
+                .map(|cause| cause.to_string())
+                .collect()
+        }
+    }
+}
+
+
+#[derive(Serialize)]
+pub struct PartReport<'a> {
+    pub part: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<&'a OutputValue>,
+    /// Left out for days solving both parts at once, where only the total is timed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ns: Option<u128>,
+    /// Only with `--profile-mem`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemoryStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorReport>
+}
+
+#[derive(Serialize)]
+pub struct DayReport<'a> {
+    pub year: i32,
+    pub day: u32,
+    pub parts: Vec<PartReport<'a>>,
+    /// Left out for days solving both parts at once, and days that didn't run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_duration_ns: Option<u128>,
+    /// `null` for days that didn't run.
+    pub total_duration_ns: Option<u128>,
+    /// Only with `--profile-mem`, covering the whole day when it solves both parts at once.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_memory: Option<MemoryStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorReport>
+}
+
+impl<'a> DayReport<'a> {
+    pub fn new(run: &'a DayRun) -> Self {
+        let nanos = |duration: Duration| Some(duration.as_nanos());
+
+        let (parts, error) = match &run.outcome {
+            RunOutcome::Solved(parts) => (parts
+                .iter()
+                .map(|part| PartReport {
+                    part: part.part.number(),
+                    value: part.result.as_ref().ok(),
+                    duration_ns: if run.separate_parts { nanos(part.duration) } else { None },
+                    memory: part.memory.filter(|_| run.separate_parts),
+                    error: part.result
+                        .as_ref()
+                        .err()
+                        .map(|err| ErrorReport::from_report(ErrorKind::Part, err))
+                })
+                .collect(), None),
+            RunOutcome::Failed(err) => (Vec::new(), Some(ErrorReport::from_report(ErrorKind::Parse, err))),
+            RunOutcome::MissingInput(path) => (Vec::new(), Some(ErrorReport::new(
+                ErrorKind::MissingInput,
+                format!("No input at {}", path.display())
+            ))),
+            RunOutcome::TimedOut(timeout) => (Vec::new(), Some(ErrorReport::new(
+                ErrorKind::TimedOut,
+                format!("Solver {timeout}")
+            )))
+        };
+
+        let missing = matches!(run.outcome, RunOutcome::MissingInput(_) | RunOutcome::TimedOut(_));
+        Self {
+            year: run.year,
+            day: run.day,
+            parts,
+            parse_duration_ns: if run.separate_parts && !missing { nanos(run.parse_duration) } else { None },
+            total_duration_ns: if missing { None } else { nanos(run.total_duration()) },
+            parse_memory: run.parse_memory,
+            error
+        }
+    }
+
+    /// Report of a day that could not be run.
+    pub fn failed(year: i32, day: u32, error: ErrorReport) -> Self {
+        Self {
+            year,
+            day,
+            parts: Vec::new(),
+            parse_duration_ns: None,
+            total_duration_ns: None,
+            parse_memory: None,
+            error: Some(error)
+        }
+    }
+}
+
+
+/// Prints the report of a single day as JSON.
+pub fn print_day(report: &DayReport) {
+    println!("{}", serde_json::to_string_pretty(report).expect("Reports are always serializable"));
+}
+
+/// Error that happened before any day could be run.
+#[derive(Serialize)]
+pub struct FailureReport {
+    pub error: ErrorReport
+}
+
+/// Prints an error that happened before any day could be run, as a JSON object with an `error`.
+pub fn print_error(error: ErrorReport) {
+    println!("{}", serde_json::to_string_pretty(&FailureReport { error }).expect("Reports are always serializable"));
+}
+
+/// Prints the reports of all runs as a JSON array.
+pub fn print_all(runs: &[DayRun]) {
+    let reports = runs
+        .iter()
+        .map(DayReport::new)
+        .collect::<Vec<_>>();
+    println!("{}", serde_json::to_string_pretty(&reports).expect("Reports are always serializable"));
+}
+
+
+#[test]
+fn test() {
+    use color_eyre::eyre::eyre;
+    use serde_json::{json, to_value};
+    use crate::{runner::PartRun, solver::Part};
+
+    let run = |separate_parts, outcome| DayRun::test(1, separate_parts, outcome);
+    let parts = || vec![
+        PartRun::test(Part::One, Ok(11u32.into()), 10),
+        PartRun::test(Part::Two, Err(eyre!("outer").wrap_err("wrapped")), 20)
+    ];
+
+    let separate = run(true, RunOutcome::Solved(parts()));
+    assert_eq!(to_value(DayReport::new(&separate)).unwrap(), json!({
+        "year": 2024,
+        "day": 1,
+        "parts": [
+            { "part": 1, "value": { "type": "U32", "value": 11 }, "duration_ns": 10 },
+            { "part": 2, "duration_ns": 20, "error": { "kind": "part", "message": "wrapped", "causes": ["outer"] } }
+        ],
+        "parse_duration_ns": 5,
+        "total_duration_ns": 35
+    }));
+
+    let combined = run(false, RunOutcome::Solved(parts()));
+    let report = to_value(DayReport::new(&combined)).unwrap();
+    assert_eq!(report["parts"][0], json!({ "part": 1, "value": { "type": "U32", "value": 11 } }));
+    assert!(report.get("parse_duration_ns").is_none());
+    assert_eq!(report["total_duration_ns"], 35);
+
+    let panicked = run(true, RunOutcome::Failed(SolverPanic { message: "oops".to_string(), location: None }.into()));
+    assert_eq!(to_value(DayReport::new(&panicked)).unwrap()["error"]["kind"], "panic");
+
+    let missing = run(true, RunOutcome::MissingInput("inputs/2024/day01.txt".into()));
+    let report = to_value(DayReport::new(&missing)).unwrap();
+    assert_eq!(report["error"]["kind"], "missing_input");
+    assert_eq!(report["total_duration_ns"], json!(null));
+
+    let failure = FailureReport { error: ErrorReport::new(ErrorKind::UnknownPuzzle, "No such day") };
+    assert_eq!(to_value(failure).unwrap(), json!({ "error": { "kind": "unknown_puzzle", "message": "No such day", "causes": [] } }));
+}
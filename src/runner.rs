@@ -0,0 +1,416 @@
+use std::{fmt::{self, Display}, fs::File, io::{self, BufReader, IsTerminal}, path::{Path, PathBuf}, sync::mpsc::{self, RecvTimeoutError}, thread, time::{Duration, Instant}};
+use color_eyre::eyre::{eyre, Report, Result, WrapErr};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use crate::{memory::{self, Bytes, MemoryStats}, misc::{panic::catch_panic, progress::ProgressScope}, params::Params, solver::{Part, Solver}, Input, PartOutput};
+
+
+/// Location of a day's input inside the inputs directory,
+/// `{inputs}/{year}/day{NN}.txt`.
+pub fn day_input_path(inputs: &Path, year: i32, day: u32) -> PathBuf {
+    inputs
+        .join(year.to_string())
+        .join(format!("day{day:0>2}.txt"))
+}
+
+/// Stdin when it is piped rather than a terminal, the fallback of [`open_input`].
+pub fn piped_stdin() -> Option<Input> {
+    let stdin = io::stdin();
+    (!stdin.is_terminal()).then(|| BufReader::new(Box::new(stdin) as Box<dyn io::Read + Send>))
+}
+
+/// Opens the input of a single day.
+///
+/// An explicit path of `-` reads stdin, without an explicit path the input is read from
+/// the inputs directory, falling back to `piped` when the file doesn't exist.
+pub fn open_input(explicit: Option<&Path>, inputs: &Path, year: i32, day: u32, piped: Option<Input>) -> Result<Input> {
+    let path = match explicit {
+        Some(path) if path == Path::new("-") => return Ok(piped.unwrap_or_else(|| BufReader::new(Box::new(io::stdin())))),
+        Some(path) => path.to_path_buf(),
+        None => {
+            let path = day_input_path(inputs, year, day);
+            if !path.exists() {
+                return piped.ok_or_else(|| eyre!(
+                    "No input found at {}, download it there, pass --input or pipe it through stdin",
+                    path.display()
+                ));
+            }
+            path
+        }
+    };
+
+    let file = File::open(&path).wrap_err_with(|| format!("Could not open input {}", path.display()))?;
+    Ok(BufReader::new(Box::new(file)))
+}
+
+
+pub struct PartRun {
+    pub part: Part,
+    pub result: PartOutput,
+    pub duration: Duration,
+    /// Only measured when profiling memory.
+    pub memory: Option<MemoryStats>
+}
+
+/// A day that didn't finish in time.
+#[derive(Debug, Clone, Copy)]
+pub struct Timeout {
+    pub after: Duration,
+    /// Position and length of the last progress bar of the day, if it made one.
+    pub progress: Option<(u64, Option<u64>)>
+}
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {:.2?}", self.after)?;
+        match self.progress {
+            Some((position, Some(length))) => write!(f, " at {position}/{length}"),
+            Some((position, None)) => write!(f, " at {position}"),
+            None => Ok(())
+        }
+    }
+}
+
+pub enum RunOutcome {
+    Solved(Vec<PartRun>),
+    Failed(Report),
+    MissingInput(PathBuf),
+    TimedOut(Timeout)
+}
+
+pub struct DayRun {
+    pub year: i32,
+    pub day: u32,
+    /// Whether the parts were timed separately from parsing.
+    pub separate_parts: bool,
+    pub outcome: RunOutcome,
+    pub parse_duration: Duration,
+    /// Only measured when profiling memory.
+    pub parse_memory: Option<MemoryStats>
+}
+
+impl DayRun {
+    /// A day that didn't get to run.
+    fn not_run(solver: &dyn Solver, outcome: RunOutcome) -> Self {
+        Self {
+            year: solver.year(),
+            day: solver.day(),
+            separate_parts: solver.has_separate_parts(),
+            outcome,
+            parse_duration: Duration::ZERO,
+            parse_memory: None
+        }
+    }
+
+    pub fn part(&self, part: Part) -> Option<&PartRun> {
+        match &self.outcome {
+            RunOutcome::Solved(parts) => parts.iter().find(|run| run.part == part),
+            _ => None
+        }
+    }
+
+    /// Whether the day or one of its parts failed or timed out, a missing input isn't a failure.
+    pub fn has_failed(&self) -> bool {
+        match &self.outcome {
+            RunOutcome::Solved(parts) => parts.iter().any(|part| part.result.is_err()),
+            RunOutcome::Failed(_) | RunOutcome::TimedOut(_) => true,
+            RunOutcome::MissingInput(_) => false
+        }
+    }
+
+    pub fn total_duration(&self) -> Duration {
+        let parts = match &self.outcome {
+            RunOutcome::Solved(parts) => parts.iter().map(|run| run.duration).sum(),
+            _ => Duration::ZERO
+        };
+        self.parse_duration + parts
+    }
+}
+
+#[cfg(test)]
+impl DayRun {
+    /// A run of a day of 2024 for tests, parsing in 5 ns.
+    pub fn test(day: u32, separate_parts: bool, outcome: RunOutcome) -> Self {
+        Self {
+            year: 2024,
+            day,
+            separate_parts,
+            outcome,
+            parse_duration: Duration::from_nanos(5),
+            parse_memory: None
+        }
+    }
+}
+
+#[cfg(test)]
+impl PartRun {
+    /// A part run for tests, taking `nanos` to solve.
+    pub fn test(part: Part, result: PartOutput, nanos: u64) -> Self {
+        Self {
+            part,
+            result,
+            duration: Duration::from_nanos(nanos),
+            memory: None
+        }
+    }
+}
+
+/// Runs the given parts of a single day, timing the parsing and each part,
+/// and measuring their allocations when profiling memory.
+///
+/// Panics of the solver become errors of the parsing or part that panicked.
+pub fn run_day(solver: &dyn Solver, input: Input, params: &Params, parts: &[Part]) -> DayRun {
+    let ((parsed, parse_duration), parse_memory) = memory::measure(|| {
+        let start = Instant::now();
+        (catch_panic(|| solver.parse(input, params)), start.elapsed())
+    });
+
+    let outcome = match parsed {
+        Ok(parsed) => RunOutcome::Solved(parts
+            .iter()
+            .filter_map(|part| {
+                let ((result, duration), memory) = memory::measure(|| {
+                    let start = Instant::now();
+                    let result = catch_panic(|| Ok(solver.solve_part(&parsed, *part)));
+                    (result.unwrap_or_else(|err| Some(Err(err))), start.elapsed())
+                });
+                Some(PartRun {
+                    part: *part,
+                    result: result?,
+                    duration,
+                    memory
+                })
+            })
+            .collect()),
+        Err(err) => RunOutcome::Failed(err)
+    };
+
+    DayRun {
+        year: solver.year(),
+        day: solver.day(),
+        separate_parts: solver.has_separate_parts(),
+        outcome,
+        parse_duration,
+        parse_memory
+    }
+}
+
+/// Runs a single day like [`run_day`], with its progress bars labelled,
+/// giving up on it when it takes longer than the timeout.
+///
+/// Threads can't be stopped, so a day that timed out keeps running in the background until the process exits.
+pub fn run_day_with_timeout(solver: &'static dyn Solver, input: Input, params: Params, parts: &[Part], label: String, timeout: Option<Duration>) -> DayRun {
+    let scope = ProgressScope::new(label);
+    let Some(timeout) = timeout else {
+        return scope.run(|| run_day(solver, input, &params, parts));
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let parts = parts.to_vec();
+    let day_scope = scope.clone();
+    thread::spawn(move || {
+        let run = day_scope.run(|| run_day(solver, input, &params, &parts));
+        // the watchdog may have stopped waiting already
+        let _ = sender.send(run);
+    });
+
+    let outcome = match receiver.recv_timeout(timeout) {
+        Ok(run) => return run,
+        Err(RecvTimeoutError::Timeout) => {
+            let bars = scope.bars();
+            for bar in &bars {
+                bar.abandon_with_message("timed out");
+            }
+            RunOutcome::TimedOut(Timeout {
+                after: timeout,
+                progress: bars.last().map(|bar| (bar.position(), bar.length()))
+            })
+        },
+        Err(RecvTimeoutError::Disconnected) => RunOutcome::Failed(eyre!("The solver crashed"))
+    };
+
+    DayRun::not_run(solver, outcome)
+}
+
+/// Runs each of the given solvers with their default parameters on the rayon pool,
+/// or one after the other when profiling memory, reading their input from the inputs directory.
+///
+/// The runs are returned in the order of the solvers.
+pub fn run_all(solvers: &[&'static dyn Solver], inputs: &Path, parts: &[Part], timeout: Option<Duration>) -> Vec<DayRun> {
+    let run = |solver: &&'static dyn Solver| {
+        let path = day_input_path(inputs, solver.year(), solver.day());
+        let Ok(file) = File::open(&path) else {
+            return DayRun::not_run(*solver, RunOutcome::MissingInput(path));
+        };
+
+        let input: Input = BufReader::new(Box::new(file));
+        let label = format!("{} day {:>2}", solver.year(), solver.day());
+        run_day_with_timeout(*solver, input, Params::defaults(solver.params()), parts, label, timeout)
+    };
+
+    // allocations are counted for the whole process, so days can't share it
+    if memory::is_enabled() {
+        solvers.iter().map(run).collect()
+    } else {
+        solvers.par_iter().map(run).collect()
+    }
+}
+
+
+fn format_duration(duration: Duration) -> String {
+    format!("{duration:.2?}")
+}
+
+/// Prints a row of a table, with the cells in the `left` columns left aligned and the others right aligned.
+fn print_row(row: &[String], widths: &[usize], left: &[usize]) {
+    let line = row
+        .iter()
+        .zip(widths)
+        .enumerate()
+        .map(|(i, (cell, width))| match left.contains(&i) {
+            true => format!("{cell:<width$}"),
+            false => format!("{cell:>width$}")
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
+    println!("{}", line.trim_end());
+}
+
+fn print_table<const N: usize>(header: &[String; N], rows: &[[String; N]], left: &[usize]) {
+    let mut widths = header.clone().map(|cell| cell.len());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    print_row(header, &widths, left);
+    for row in rows {
+        print_row(row, &widths, left);
+    }
+}
+
+/// Prints a table with the answers and time taken for each run,
+/// followed by the totals and any errors.
+///
+/// Days solving both parts at once only have a total time.
+pub fn print_summary(runs: &[DayRun]) {
+    let rows = runs
+        .iter()
+        .map(|run| {
+            let answer = |part| match (&run.outcome, run.part(part)) {
+                (RunOutcome::Failed(_), _) => "error".to_string(),
+                (RunOutcome::MissingInput(_), _) => "no input".to_string(),
+                (RunOutcome::TimedOut(_), _) => "timed out".to_string(),
+                (_, Some(PartRun { result: Ok(value), .. })) => value.to_string(),
+                (_, Some(PartRun { result: Err(_), .. })) => "error".to_string(),
+                (_, None) => "-".to_string()
+            };
+            let time = |part| match run.part(part) {
+                Some(part_run) if run.separate_parts => format_duration(part_run.duration),
+                _ => "-".to_string()
+            };
+
+            let (parse, total) = match run.outcome {
+                RunOutcome::MissingInput(_) | RunOutcome::TimedOut(_) => ("-".to_string(), "-".to_string()),
+                _ if !run.separate_parts => ("-".to_string(), format_duration(run.total_duration())),
+                _ => (format_duration(run.parse_duration), format_duration(run.total_duration()))
+            };
+
+            [
+                run.year.to_string(), run.day.to_string(),
+                answer(Part::One), answer(Part::Two),
+                parse, time(Part::One), time(Part::Two), total
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let header = ["Year", "Day", "Part 1", "Part 2", "Parse", "Time 1", "Time 2", "Total"].map(String::from);
+    // answers are left aligned, numbers and durations right aligned
+    print_table(&header, &rows, &[0, 2, 3]);
+
+    let timed_out = runs
+        .iter()
+        .filter(|run| matches!(run.outcome, RunOutcome::TimedOut(_)))
+        .count();
+    let missing = runs
+        .iter()
+        .filter(|run| matches!(run.outcome, RunOutcome::MissingInput(_)))
+        .count();
+    let failed_count = runs
+        .iter()
+        .filter(|run| run.has_failed() && !matches!(run.outcome, RunOutcome::TimedOut(_)))
+        .count();
+    let solved = runs.len() - missing - failed_count - timed_out;
+    let total: Duration = runs.iter().map(DayRun::total_duration).sum();
+
+    println!(
+        "\nSolved {solved}/{} days in {total:.2?} ({failed_count} failed, {timed_out} timed out, {missing} without input)\n",
+        runs.len()
+    );
+
+    for run in runs {
+        match &run.outcome {
+            RunOutcome::Solved(parts) => for part in parts {
+                if let Err(err) = &part.result {
+                    eprintln!("Day {} of year {} failed in part {}: {err}", run.day, run.year, part.part);
+                }
+            },
+            RunOutcome::Failed(err) => eprintln!("Day {} of year {} failed: {err}", run.day, run.year),
+            RunOutcome::MissingInput(path) => eprintln!("Day {} of year {} has no input at {}", run.day, run.year, path.display()),
+            RunOutcome::TimedOut(timeout) => eprintln!("Day {} of year {} {timeout}", run.day, run.year)
+        }
+    }
+}
+
+/// Prints a table with the allocations of the parsing and each part of the runs that were profiled.
+///
+/// Days solving both parts at once only have a total.
+pub fn print_memory(runs: &[DayRun]) {
+    let row = |run: &DayRun, stage: &str, memory: &MemoryStats| [
+        run.year.to_string(), run.day.to_string(), stage.to_string(),
+        memory.allocations.to_string(), Bytes(memory.bytes).to_string(), Bytes(memory.peak_heap_bytes).to_string()
+    ];
+
+    let mut rows = Vec::new();
+    for run in runs {
+        if let Some(memory) = &run.parse_memory {
+            rows.push(row(run, if run.separate_parts { "parse" } else { "total" }, memory));
+        }
+        if let RunOutcome::Solved(parts) = &run.outcome {
+            for part in parts.iter().filter(|_| run.separate_parts) {
+                if let Some(memory) = &part.memory {
+                    rows.push(row(run, &format!("part {}", part.part), memory));
+                }
+            }
+        }
+    }
+
+    println!("\nMemory\n");
+    let header = ["Year", "Day", "Stage", "Allocations", "Allocated", "Peak heap"].map(String::from);
+    print_table(&header, &rows, &[2]);
+}
+
+
+#[test]
+fn test() {
+    use std::{fs, io::Read};
+    use crate::misc::test::temp_dir;
+
+    let inputs = temp_dir("runner");
+    let piped = |text: &'static str| Some(BufReader::new(Box::new(text.as_bytes()) as Box<dyn Read + Send>));
+    let err = open_input(None, &inputs, 2024, 1, None).err().unwrap();
+    assert!(err.to_string().contains(&day_input_path(&inputs, 2024, 1).display().to_string()));
+    assert!(open_input(Some(&inputs.join("missing.txt")), &inputs, 2024, 1, piped("3 4\n")).is_err());
+
+    let mut input = String::new();
+    open_input(None, &inputs, 2024, 1, piped("3 4\n")).unwrap().read_to_string(&mut input).unwrap();
+    assert_eq!(input, "3 4\n");
+
+    let path = day_input_path(&inputs, 2024, 1);
+    fs::create_dir(path.parent().unwrap()).unwrap();
+    fs::write(&path, "1 2\n").unwrap();
+    let mut input = String::new();
+    open_input(None, &inputs, 2024, 1, piped("3 4\n")).unwrap().read_to_string(&mut input).unwrap();
+    fs::remove_dir_all(&inputs).unwrap();
+    assert_eq!(input, "1 2\n");
+}
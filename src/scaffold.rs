@@ -0,0 +1,107 @@
+use std::{fs::{self, OpenOptions}, io::{ErrorKind, Write}, path::{Path, PathBuf}};
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use crate::{calendar, solver};
+
+
+const TEMPLATE: &str = include_str!("solutions/template.txt");
+
+
+/// Directory of the day files of the given year relative to the crate root,
+/// where the registered days of the year are or `src/y{year}` for a year without any.
+pub fn year_dir(year: i32) -> PathBuf {
+    match solver::year_dir(year) {
+        Some(dir) => dir.to_path_buf(),
+        None => PathBuf::from(format!("src/y{year}"))
+    }
+}
+
+pub fn day_path(root: &Path, year: i32, day: u32) -> Result<PathBuf> {
+    calendar::validate(year, day)?;
+    Ok(root.join(year_dir(year)).join(format!("d{day}.rs")))
+}
+
+/// The template with the registration of the given day filled in.
+pub fn day_source(year: i32, day: u32, title: &str) -> String {
+    TEMPLATE.replacen("YEAR, DAY, \"TITLE\"", &format!("{year}, {day}, {title:?}"), 1)
+}
+
+/// Creates the directory of a year's day files with a `mod.rs` including all of them,
+/// and declares its module in `src/lib.rs` after the other years.
+fn add_year(root: &Path, dir: &Path) -> Result<()> {
+    let module = dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| eyre!("Invalid year directory {}", dir.display()))?;
+
+    let lib_path = root.join("src/lib.rs");
+    let lib = fs::read_to_string(&lib_path).wrap_err_with(|| format!("Could not read {}", lib_path.display()))?;
+    let mut lines = lib.lines().collect::<Vec<_>>();
+    // the years are the only private modules
+    let Some(last_year) = lines.iter().rposition(|line| line.starts_with("mod ")) else {
+        bail!("{} declares no year modules to add {module} after", lib_path.display());
+    };
+    let declaration = format!("mod {module};");
+    lines.insert(last_year + 1, &declaration);
+    let lib = lines.join("\n") + "\n";
+
+    fs::create_dir_all(root.join(dir))?;
+    let dir_name = dir.to_str().expect("Year directories are valid UTF-8").replace('\\', "/");
+    fs::write(root.join(dir).join("mod.rs"), format!("automod::dir!({dir_name:?});\n"))?;
+    fs::write(&lib_path, lib).wrap_err_with(|| format!("Could not write {}", lib_path.display()))
+}
+
+/// Writes a new day file from the template into the crate at `root`, refusing to overwrite an existing one.
+///
+/// The day registers itself, so it is picked up without any other changes.
+/// The first day of a year also adds the year's module.
+pub fn new_day(root: &Path, year: i32, day: u32, title: &str) -> Result<PathBuf> {
+    if !root.join("src/lib.rs").exists() {
+        bail!("{} is not the root of the solutions crate, it has no src/lib.rs", root.display());
+    }
+    let path = day_path(root, year, day)?;
+    let dir = year_dir(year);
+    if !root.join(&dir).exists() {
+        add_year(root, &dir)?;
+    }
+
+    let mut file = match OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::AlreadyExists => bail!("{} already exists, not overwriting it", path.display()),
+        Err(err) => return Err(err).wrap_err_with(|| format!("Could not create {}", path.display()))
+    };
+    file.write_all(day_source(year, day, title).as_bytes())?;
+
+    Ok(path)
+}
+
+
+#[test]
+fn test() {
+    use crate::misc::test::temp_dir;
+
+    let root = temp_dir("scaffold");
+    assert!(new_day(&root, 2024, 5, "Print Queue").is_err(), "Not a crate root");
+
+    fs::create_dir_all(root.join("src/solutions")).unwrap();
+    fs::write(root.join("src/lib.rs"), "mod solutions;\npub mod misc;\n").unwrap();
+
+    assert!(new_day(&root, 2024, 30, "Title").is_err(), "2024 has no day 30");
+
+    let path = new_day(&root, 2024, 5, "Print Queue").unwrap();
+    assert_eq!(path, root.join("src/solutions/d5.rs"));
+    let source = fs::read_to_string(&path).unwrap();
+    assert!(source.contains("register_day!(2024, 5, \"Print Queue\", parse, part1, part2);"));
+
+    fs::write(&path, "changed").unwrap();
+    assert!(new_day(&root, 2024, 5, "Print Queue").is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "changed", "Existing days are never overwritten");
+
+    let path = new_day(&root, 2026, 1, "Day 1").unwrap();
+    assert_eq!(path, root.join("src/y2026/d1.rs"));
+    assert_eq!(fs::read_to_string(root.join("src/y2026/mod.rs")).unwrap(), "automod::dir!(\"src/y2026\");\n");
+    assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), "mod solutions;\nmod y2026;\npub mod misc;\n");
+    new_day(&root, 2026, 2, "Day 2").unwrap();
+    assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), "mod solutions;\nmod y2026;\npub mod misc;\n");
+
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -1,5 +1,8 @@
 use std::io::BufRead;
-use crate::{misc::option::OptionExt, output, Input, Output};
+use crate::{misc::option::OptionExt, output, register_day, Input, Output};
+
+
+register_day!(2024, 1, "Historian Hysteria", solve);
 
 
 pub fn solve(input: Input) -> Output {
@@ -33,14 +36,7 @@ pub fn solve(input: Input) -> Output {
 
 #[test]
 fn test() {
-    use crate::misc::test::test_solver;
-
-    test_solver(solve, indoc::indoc! {"
-        3   4
-        4   3
-        2   5
-        1   3
-        3   9
-        3   3
-    "}, output!(11, 31));
+    use crate::misc::test::test_examples;
+
+    test_examples(2024, 1);
 }
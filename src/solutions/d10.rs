@@ -1,62 +1,35 @@
-use std::{collections::{HashMap, VecDeque}, io::BufRead};
-use crate::{misc::{grid::Grid, option::OptionExt, vector2::Directions}, output, Input, Output};
+use std::io::BufRead;
+use crate::{misc::{grid::Grid, pathfinding::bfs}, output, register_day, Input, Output};
 
 
-fn char_to_num(c: u8) -> u8 {
-    c - b'0'
-}
+register_day!(2024, 10, "Hoof It", solve);
+
 
 pub fn solve(input: Input) -> Output {
-    let mut map = Grid::from(input
+    let map = Grid::from(input
         .lines()
         .map(|line| line.unwrap())
-    )?;
-
-    let mut queue = VecDeque::new();
-    let mut ends = HashMap::new();
-
-    for (x, y, value) in map
-        .iter_signed()
-        .filter(|(_, _, value)| *value == b'0') {
-        queue.push_back((x, y, value, (x, y)));
-        ends.insert((x, y), Vec::new());
-    }
-
+    )?.digits();
 
     let mut routes = 0;
     let mut full_routes = 0;
 
-    while let Some((x, y, value, start)) = queue.pop_front() {
-        map.signed_set(x, y, b'X');
-
-        let num = char_to_num(value);
-
-        for (dx, dy) in isize::DIRECTIONS {
-            let (x2, y2) = (x + dx, y + dy);
-
-            let Some(next_value) = map.signed_get(x2, y2) else {
-                continue;
-            };
-            if !next_value.is_ascii_digit() {
-                continue;
-            }
-            let next_num = char_to_num(next_value);
-
-            if num < next_num && next_num - num == 1 {
-                if next_num >= 9 {
-                    full_routes += 1;
-
-                    let route_ends = ends.get_mut(&start).unwrap_or_err()?;
-                    if route_ends.contains(&(x2, y2)) {
-                        continue;
-                    }
-                    route_ends.push((x2, y2));
-                    routes += 1;
-
-                } else {
-                    queue.push_back((x2, y2, next_value, start));
-                }
-            }
+    for (x, y, _) in map
+        .iter_signed()
+        .filter(|(_, _, value)| *value == Some(0)) {
+        // every step climbs by one, so all trails to a top are shortest paths
+        let trails = bfs(
+            [(x, y, 0)],
+            |&(x, y, num)| map
+                .neighbours(x, y)
+                .filter_map(|(x2, y2, _, next_num)| Some((x2, y2, next_num?)))
+                .filter(move |(_, _, next_num)| *next_num == num + 1),
+            |_| false
+        );
+
+        for top in trails.distances.keys().filter(|(_, _, num)| *num == 9) {
+            routes += 1;
+            full_routes += trails.path_count(top);
         }
     }
 
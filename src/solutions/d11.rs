@@ -1,6 +1,9 @@
 use std::{collections::HashMap, io::BufRead};
 use color_eyre::eyre::Result;
-use crate::{misc::option::OptionExt, output, Input, Output};
+use crate::{misc::option::OptionExt, output, register_day, Input, Output};
+
+
+register_day!(2024, 11, "Plutonian Pebbles", solve);
 
 
 fn count_stones(input: &[i64], depth: u64, previous_steps: &[i64], cache: &mut HashMap<i64, HashMap<u64, usize>>) -> Result<usize> {
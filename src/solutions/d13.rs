@@ -3,7 +3,10 @@ use color_eyre::eyre::Result;
 use itertools::Itertools;
 use regex::Regex;
 use regex_macro::regex;
-use crate::{misc::option::OptionExt, output, Input, Output};
+use crate::{misc::option::OptionExt, output, register_day, Input, Output};
+
+
+register_day!(2024, 13, "Claw Contraption", solve);
 
 
 fn parse_coords(regex: &Regex, s: &str) -> Result<(isize, isize)> {
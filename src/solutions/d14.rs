@@ -1,6 +1,16 @@
 use std::{cmp::Ordering, io::BufRead};
 use color_eyre::eyre::Result;
-use crate::{misc::{grid::Grid, option::OptionExt, progress::pretty_progress_bar}, output, Input, Output};
+use log::{debug, info};
+use crate::{misc::{grid::Grid, option::OptionExt, progress::pretty_progress_bar}, output, params::{Param, Params}, register_day, Input, PartOutput};
+
+
+register_day!(2024, 14, "Restroom Redoubt", params = PARAMS, parse, part1, part2);
+
+const PARAMS: &[Param] = &[
+    Param { name: "width", default: "101", help: "Width of the bathroom" },
+    Param { name: "height", default: "103", help: "Height of the bathroom" },
+    Param { name: "time", default: "100", help: "Seconds the robots move before counting them in part 1" }
+];
 
 
 fn parse_pos(line: &str) -> Result<(isize, isize)> {
@@ -17,11 +27,11 @@ struct Robot {
 }
 
 fn check_christmas_tree(robots: &[Robot], width: isize, height: isize) -> bool {
-    let mut map = Grid::from_size(width as usize, height as usize, b'0');
+    let mut map = Grid::from_size(width as usize, height as usize, 0u32);
     for robot in robots.iter() {
         let (x, y) = robot.pos;
         let current = map.signed_get_or_default(x, y);
-        map.signed_set(x, y, (current - b'0' + 1).to_string().as_bytes()[0]);
+        map.signed_set(x, y, current + 1);
     }
 
     let min_height = 3;
@@ -32,7 +42,7 @@ fn check_christmas_tree(robots: &[Robot], width: isize, height: isize) -> bool {
         'search: loop {
             for dx in -height..=height {
                 let found = map.signed_get_or_default(x + dx, y + height);
-                if found == b'0' || found == b'\0' {
+                if found == 0 {
                     break 'search;
                 }
             }
@@ -40,7 +50,12 @@ fn check_christmas_tree(robots: &[Robot], width: isize, height: isize) -> bool {
         }
 
         if height >= min_height {
-            println!("{}", map);
+            let picture = map.map(|count| match count {
+                0 => b'.',
+                1..=9 => b'0' + count as u8,
+                _ => b'+'
+            });
+            info!(target: LOG_TARGET, "Found a tree of height {height} at {x},{y}\n{picture}");
             return true;
         }
     }
@@ -48,26 +63,44 @@ fn check_christmas_tree(robots: &[Robot], width: isize, height: isize) -> bool {
 }
 
 
-pub fn solve(input: Input) -> Output {
-    let mut robots_per_quadrant = [[0; 2]; 2];
-
-    let (width, height) = (101, 103);
-    let (middle_x, middle_y) = (width / 2, height / 2);
+pub struct Bathroom {
+    robots: Vec<Robot>,
+    width: isize,
+    height: isize,
+    time: isize
+}
 
-    let mut robots = Vec::<Robot>::new();
+pub fn parse(input: Input, params: &Params) -> Result<Bathroom> {
+    let mut robots = Vec::new();
 
-    let time = 100;
     for line in input.lines() {
         let line = line?;
 
         let (pos, velocity) = line.split_once(' ').unwrap_or_err()?;
-        let (mut x, mut y) = parse_pos(&pos[2..])?;
-        let (dx, dy) = parse_pos(&velocity[2..])?;
-
         robots.push(Robot {
-            pos: (x, y),
-            velocity: (dx, dy)
+            pos: parse_pos(&pos[2..])?,
+            velocity: parse_pos(&velocity[2..])?
         });
+    }
+
+    Ok(Bathroom {
+        robots,
+        width: params.get("width")?,
+        height: params.get("height")?,
+        time: params.get("time")?
+    })
+}
+
+pub fn part1(bathroom: &Bathroom) -> PartOutput {
+    let mut robots_per_quadrant = [[0; 2]; 2];
+
+    let (width, height) = (bathroom.width, bathroom.height);
+    let (middle_x, middle_y) = (width / 2, height / 2);
+
+    let time = bathroom.time;
+    for robot in &bathroom.robots {
+        let (mut x, mut y) = robot.pos;
+        let (dx, dy) = robot.velocity;
 
         x = (x + dx * time).rem_euclid(width);
         y = (y + dy * time).rem_euclid(height);
@@ -86,6 +119,15 @@ pub fn solve(input: Input) -> Output {
         robots_per_quadrant[quadrant_x][quadrant_y] += 1;
     }
 
+    debug!(target: LOG_TARGET, "Robots per quadrant {:?} {:?}", robots_per_quadrant[0], robots_per_quadrant[1]);
+    let result = robots_per_quadrant[0][0] * robots_per_quadrant[0][1] * robots_per_quadrant[1][0] * robots_per_quadrant[1][1];
+    output!(result)
+}
+
+pub fn part2(bathroom: &Bathroom) -> PartOutput {
+    let (width, height) = (bathroom.width, bathroom.height);
+    let mut robots = bathroom.robots.clone();
+
     let mut i = 0;
     let max_depth = 10000;
     let progress = pretty_progress_bar(max_depth as u64);
@@ -110,7 +152,27 @@ pub fn solve(input: Input) -> Output {
         i += 1;
     }
 
-    println!("{:?}\n{:?}", robots_per_quadrant[0], robots_per_quadrant[1]);
-    let result = robots_per_quadrant[0][0] * robots_per_quadrant[0][1] * robots_per_quadrant[1][0] * robots_per_quadrant[1][1];
-    output!(result, i)
+    output!(i)
+}
+
+
+#[test]
+fn test() {
+    use crate::misc::test::test_part_with;
+
+    let params = Params::new(PARAMS, &[("width", "11"), ("height", "7")]).unwrap();
+    test_part_with(parse, &params, part1, indoc::indoc! {"
+        p=0,4 v=3,-3
+        p=6,3 v=-1,-3
+        p=10,3 v=-1,2
+        p=2,0 v=2,-1
+        p=0,0 v=1,3
+        p=3,0 v=-2,-2
+        p=7,6 v=-1,-3
+        p=3,0 v=-1,-2
+        p=9,3 v=2,3
+        p=7,3 v=-1,2
+        p=2,4 v=2,-3
+        p=9,5 v=-3,-3
+    "}, output!(12));
 }
@@ -1,5 +1,8 @@
 use std::io::BufRead;
-use crate::{misc::{grid::Grid, option::OptionExt}, output, Input, Output};
+use crate::{misc::{grid::Grid, option::OptionExt}, output, register_day, Input, Output};
+
+
+register_day!(2024, 15, "Warehouse Woes", solve);
 
 
 fn direction_from_char(c: u8) -> Option<(isize, isize)> {
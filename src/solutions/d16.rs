@@ -1,14 +1,10 @@
-use std::{cmp::Reverse, collections::{HashMap, HashSet, VecDeque}, io::BufRead};
-use priority_queue::PriorityQueue;
-use crate::{misc::{grid::Grid, option::OptionExt, vector2::directions}, output, Input, Output};
+use std::{collections::HashSet, io::BufRead};
+use log::trace;
+use crate::{misc::{grid::Grid, option::OptionExt, pathfinding::{dijkstra, turning_neighbours}}, output, register_day, Input, Output};
 
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct PathStep {
-    pub pos: (isize, isize),
-    pub dir: i32,
-    pub score: usize
-}
+register_day!(2024, 16, "Reindeer Maze", solve);
+
 
 fn dir_to_char(dir: usize) -> u8 {
     match dir {
@@ -21,21 +17,7 @@ fn dir_to_char(dir: usize) -> u8 {
 }
 
 
-#[derive(Debug, Clone, Copy)]
-struct PathStepOrigins {
-    pub dirs: [usize; 4]
-}
-impl Default for PathStepOrigins {
-    fn default() -> Self {
-        Self {
-            dirs: [usize::MAX; 4]
-        }
-    }
-}
-
-
 pub fn solve(input: Input) -> Output {
-    let directions = directions::<isize>();
     let mut map = Grid::from(input
         .lines()
         .map(|line| line.unwrap())
@@ -43,99 +25,33 @@ pub fn solve(input: Input) -> Output {
 
     let start = map.find_signed(b'S').unwrap_or_err()?;
     let end = map.find_signed(b'E').unwrap_or_err()?;
-    map.signed_set(end.0, end.1, b'.');
-
-    let mut origins = HashMap::new();
-    let mut queue = PriorityQueue::new();
-    queue.push(PathStep {
-        pos: (start.0, start.1),
-        dir: 0,
-        score: 0
-    }, Reverse(0));
-    origins.insert((start.0, start.1), PathStepOrigins {
-        dirs: [0, usize::MAX, usize::MAX, usize::MAX]
-    });
-
-    while let Some((current, _)) = queue.pop() {
-        if current.pos == (end.0, end.1) {
-            break;
-        }
-        let mut origin_step = *origins.get(&current.pos).unwrap_or_err()?;
-
-        for (dir, (dx, dy)) in directions.iter().enumerate() {
-            let (nx, ny) = (current.pos.0 + dx, current.pos.1 + dy);
-            let cell = map.signed_get_or_default(nx, ny);
-            if cell == b'#' || cell == b'\0' {
-                continue;
-            }
-
-            let mut rot = (dir as i32 - current.dir).rem_euclid(directions.len() as i32) as usize;
-            rot = rot.min(directions.len() - rot);
-            if rot != 0 && origin_step.dirs[dir] > current.score + 1000 * rot {
-                origin_step.dirs[dir] = current.score + 1000 * rot;
-            }
-
-            let step = PathStep {
-                pos: (nx, ny),
-                dir: dir as i32,
-                score: current.score + 1 + 1000 * rot
-            };
-            let origin = origins.entry((nx, ny)).or_default();
-            if step.score < origin.dirs[dir] {
-                origin.dirs[dir] = step.score;
-                queue.push(step, Reverse(step.score));
-                map.signed_set(nx, ny, dir_to_char(dir));
-            }
-        }
-
-        origins.insert(current.pos, origin_step);
-    }
-    let end_origins = *origins
-        .get(&(end.0, end.1))
-        .unwrap_or_err()?;
-    let score = *end_origins
-        .dirs
-        .iter()
-        .min()
-        .unwrap_or_err()?;
-    dbg!(&map);
-
-
-    let mut path = HashSet::new();
-    let mut queue = VecDeque::new();
-    for (dir, dir_score) in end_origins.dirs.iter().enumerate() {
-        if score == *dir_score {
-            queue.push_back((end.0, end.1, score, dir));
-        }
-    }
 
-    while let Some((x, y, score, current_dir)) = queue.pop_front() {
-        if path.contains(&(x, y)) {
-            continue;
-        }
-        path.insert((x, y));
-
-        for (dir, (dx, dy)) in directions.iter().enumerate() {
-            let (nx, ny) = (x - dx, y - dy);
-            let Some(next) = origins.get(&(nx, ny)) else {
-                continue;
-            };
-
-            let mut rot = (dir as i32 - current_dir as i32).rem_euclid(directions.len() as i32) as usize;
-            rot = rot.min(directions.len() - rot);
-
-            if next.dirs[dir] == score.wrapping_sub(1 + 1000 * rot) {
-                queue.push_back((nx, ny, next.dirs[dir], dir));
-            }
-        }
+    let paths = dijkstra(
+        [(start, 0)],
+        |facing| turning_neighbours(&map, *facing, |cell| cell == b'#', 1000),
+        |(pos, _)| *pos == end
+    );
+    let score = paths.end_distance().unwrap_or_err()?;
+
+    let mut explored = map.clone();
+    for ((x, y), dir) in paths.path_to(&paths.ends[0]).unwrap_or_err()? {
+        explored.signed_set(x, y, dir_to_char(dir));
     }
-    for (x, y) in &path {
+    trace!(target: LOG_TARGET, "Best path\n{explored}");
+
+
+    let seats = paths
+        .nodes_on_paths(paths.ends.iter().copied())
+        .into_iter()
+        .map(|(pos, _)| pos)
+        .collect::<HashSet<_>>();
+    for (x, y) in &seats {
         map.signed_set(*x, *y, b'O');
     }
-    dbg!(&map);
+    trace!(target: LOG_TARGET, "Best seats\n{map}");
 
 
-    output!(score, path.len())
+    output!(score, seats.len())
 }
 
 
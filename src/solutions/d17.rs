@@ -2,7 +2,10 @@ use std::{collections::VecDeque, io::BufRead};
 use color_eyre::eyre::Result;
 use itertools::Itertools;
 use regex_macro::regex;
-use crate::{misc::option::OptionExt, output, Input, Output};
+use crate::{misc::option::OptionExt, output, register_day, Input, Output};
+
+
+register_day!(2024, 17, "Chronospatial Computer", solve);
 
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1,40 +1,36 @@
-use std::{collections::VecDeque, io::BufRead, iter};
+use std::io::BufRead;
 use color_eyre::eyre::Result;
-use crate::{misc::{grid::Grid, option::OptionExt, progress::pretty_progress_bar, vector2::Directions}, output, Input, Output};
+use log::trace;
+use crate::{misc::{grid::Grid, option::OptionExt, pathfinding::{astar, bfs, open_neighbours, Pos}, progress::pretty_progress_bar}, output, params::{Param, Params}, register_day, Input, PartOutput};
 
 
-fn find_path(grid: &mut Grid, bytes_to_fall: &mut impl Iterator<Item = (isize, isize)>, start: (isize, isize), end: (isize, isize)) -> Result<Option<usize>> {
-    let directions = isize::DIRECTIONS;
-    let mut queue = VecDeque::new();
-    queue.push_back((start.0, start.1, 0));
+register_day!(2024, 18, "RAM Run", params = PARAMS, parse, part1, part2);
 
-    let mut last_dist = 0;
-    while let Some((x, y, dist)) = queue.pop_front() {
-        if x == end.0 && y == end.1 {
-            return Ok(Some(dist));
-        }
+const PARAMS: &[Param] = &[
+    Param { name: "size", default: "70", help: "Highest coordinate of the memory space" },
+    Param { name: "bytes", default: "1024", help: "Bytes that have fallen in part 1" }
+];
 
-        if dist > last_dist {
-            if let Some(step) = bytes_to_fall.next() {
-                grid.signed_set(step.0, step.1, b'#');
-                last_dist = dist;
-            }
-        }
 
-        for (dx, dy) in directions {
-            let (nx, ny) = (x + dx, y + dy);
+fn is_wall(cell: u8) -> bool {
+    cell == b'#'
+}
 
-            if grid.signed_get_or_default(nx, ny) == b' ' {
-                grid.signed_set(nx, ny, b'.');
-                queue.push_back((nx, ny, dist + 1));
-            }
-        }
+/// Marks cells, like those of a path, in the grid.
+fn mark_cells(grid: &mut Grid, cells: &[Pos]) {
+    for (x, y) in cells {
+        grid.signed_set(*x, *y, b'.');
     }
+}
+
 
-    Ok(None)
+pub struct FallingBytes {
+    steps: Vec<(isize, isize)>,
+    size: isize,
+    fallen: usize
 }
 
-pub fn solve(input: Input) -> Output {
+pub fn parse(input: Input, params: &Params) -> Result<FallingBytes> {
     let steps = input
         .lines()
         .map(|line| {
@@ -44,46 +40,66 @@ pub fn solve(input: Input) -> Output {
         })
         .collect::<Result<Vec<_>>>()?;
 
-    let size = 70 + 1;
+    Ok(FallingBytes {
+        steps,
+        size: params.get::<isize>("size")? + 1,
+        fallen: params.get("bytes")?
+    })
+}
+
+pub fn part1(bytes: &FallingBytes) -> PartOutput {
+    let size = bytes.size;
     let mut grid = Grid::from_size(size as usize, size as usize, b' ');
-    let mut bytes_to_fall = steps.iter();
-    for _ in 0..1024 {
-        let (x, y) = bytes_to_fall.next().unwrap_or_err()?;
+    let mut fallen = bytes.steps.iter();
+    for _ in 0..bytes.fallen {
+        let (x, y) = fallen.next().unwrap_or_err()?;
         grid.signed_set(*x, *y, b'#');
     }
 
-    let path_length = find_path(
-        &mut grid,
-        &mut bytes_to_fall.copied(),
-        (0, 0),
-        (size - 1, size - 1)
-    )?;
-    dbg!(&grid);
+    let end = (size - 1, size - 1);
+    let paths = astar(
+        [(0, 0)],
+        |pos| open_neighbours(&grid, *pos, is_wall).map(|pos| (pos, 1)),
+        |(x, y)| x.abs_diff(end.0) + y.abs_diff(end.1),
+        |pos| *pos == end
+    );
+    let path_length = paths.end_distance();
+    mark_cells(&mut grid, &paths.path_to(&end).unwrap_or_default());
+    trace!(target: LOG_TARGET, "Path after {} bytes\n{grid}", bytes.fallen);
 
+    output!(path_length.unwrap_or_err()?)
+}
 
-    grid = Grid::from_size(size as usize, size as usize, b' ');
+pub fn part2(bytes: &FallingBytes) -> PartOutput {
+    let size = bytes.size;
+    let mut grid = Grid::from_size(size as usize, size as usize, b' ');
     let mut game_over_step = (size - 1, size - 1);
-    let progress = pretty_progress_bar(steps.len() as u64);
-    for (x, y) in steps {
+    let progress = pretty_progress_bar(bytes.steps.len() as u64);
+    for (x, y) in bytes.steps.iter().copied() {
         grid.signed_set(x, y, b'#');
 
-        let mut grid = grid.clone();
-        let path_length = find_path(
-            &mut grid,
-            &mut iter::empty(),
-            (0, 0),
-            (size - 1, size - 1)
-        )?;
-        if path_length.is_none() {
+        let paths = bfs(
+            [(0, 0)],
+            |pos| open_neighbours(&grid, *pos, is_wall),
+            |pos| *pos == (size - 1, size - 1)
+        );
+        if paths.ends.is_empty() {
             game_over_step = (x, y);
-            dbg!(&grid);
+            let mut grid = grid.clone();
+            mark_cells(&mut grid, &paths.distances.keys().copied().collect::<Vec<_>>());
+            trace!(target: LOG_TARGET, "Blocked by byte {x},{y}\n{grid}");
             break;
         }
         progress.inc(1);
     }
 
-    output!(
-        path_length.unwrap_or_err()?,
-        format!("{},{}", game_over_step.0, game_over_step.1)
-    )
+    output!(format!("{},{}", game_over_step.0, game_over_step.1))
+}
+
+
+#[test]
+fn test() {
+    use crate::misc::test::test_examples;
+
+    test_examples(2024, 18);
 }
@@ -1,6 +1,9 @@
 use std::{collections::HashMap, io::BufRead};
 use trie_rs::Trie;
-use crate::{misc::option::OptionExt, output, Input, Output};
+use crate::{misc::option::OptionExt, output, register_day, Input, Output};
+
+
+register_day!(2024, 19, "Linen Layout", solve);
 
 
 /// Returns number of unique combinations of patterns that make up the remaining string.
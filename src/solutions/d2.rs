@@ -1,5 +1,8 @@
 use std::io::BufRead;
-use crate::{output, Input, Output};
+use crate::{output, register_day, Input, Output};
+
+
+register_day!(2024, 2, "Red-Nosed Reports", solve);
 
 
 fn is_safe(diff: i32, i: usize, prev_diff: i32) -> bool {
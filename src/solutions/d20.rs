@@ -1,35 +1,32 @@
-use std::{collections::{HashMap, HashSet, VecDeque}, io::BufRead};
-use crate::{misc::{grid::Grid, option::OptionExt, vector2::Directions}, output, Input, Output};
+use std::{collections::HashMap, io::BufRead};
+use crate::{misc::{grid::Grid, option::OptionExt, pathfinding::{bfs, open_neighbours, Pos}}, output, params::{Param, Params}, register_day, Input, Output};
 
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct PathState {
-    pub pos: (isize, isize),
-    pub score: isize
-}
+register_day!(2024, 20, "Race Condition", params = PARAMS, solve);
+
+const PARAMS: &[Param] = &[
+    Param { name: "saving", default: "100", help: "Picoseconds a cheat must save at least to be counted" }
+];
 
 
+fn is_wall(cell: u8) -> bool {
+    cell == b'#'
+}
+
+/// Lengths of the routes from start to end shorter than `uncheated_length`, when walls may be passed
+/// for up to `allowed_cheat_steps` once, given the distances from the start and to the end of every position.
 fn count_cheats(
-    original_map: &Grid,
-    start: (isize, isize),
+    from_start: &HashMap<Pos, usize>,
+    to_end: &HashMap<Pos, usize>,
     allowed_cheat_steps: usize,
-    route_to_end_cache: &HashMap<(isize, isize), usize>,
     uncheated_length: isize
 ) -> Vec<isize> {
     let allowed_cheat_steps = allowed_cheat_steps as isize;
-    let mut map = original_map.clone();
-    let mut visited = HashSet::new();
     let mut cheats = Vec::new();
 
-    let mut queue = VecDeque::new();
-    queue.push_back(PathState {
-        pos: (start.0, start.1),
-        score: 0
-    });
-
-    while let Some(current) = queue.pop_front() {
-        visited.insert(current.pos);
-        if current.score >= uncheated_length {
+    for (pos, score) in from_start {
+        let score = *score as isize;
+        if score >= uncheated_length {
             continue;
         }
 
@@ -39,98 +36,74 @@ fn count_cheats(
                 if steps == 0 || steps > allowed_cheat_steps {
                     continue;
                 }
-                let next = (current.pos.0 + dx, current.pos.1 + dy);
-                let Some(remaining) = route_to_end_cache.get(&next) else {
+                let next = (pos.0 + dx, pos.1 + dy);
+                let Some(remaining) = to_end.get(&next) else {
                     continue;
                 };
-                let score = current.score + steps + *remaining as isize;
+                let score = score + steps + *remaining as isize;
                 if score < uncheated_length {
                     cheats.push(score);
                 }
             }
         }
-
-        for (dx, dy) in isize::DIRECTIONS {
-            let (nx, ny) = (current.pos.0 + dx, current.pos.1 + dy);
-            if visited.contains(&(nx, ny)) {
-                continue;
-            }
-            let cell = original_map.signed_get_or_default(nx, ny);
-            if cell == b'\0' || cell == b'#' {
-                continue;
-            }
-
-            let step = PathState {
-                pos: (nx, ny),
-                score: current.score + 1,
-            };
-            if step.score > uncheated_length {
-                continue;
-            }
-
-            queue.push_back(step);
-            map.signed_set(nx, ny, b'*');
-        }
     }
 
     cheats
 }
 
 
-pub fn solve(input: Input) -> Output {
-    let mut original_map = Grid::from(input
+pub fn solve(input: Input, params: &Params) -> Output {
+    let saving: isize = params.get("saving")?;
+    let map = Grid::from(input
         .lines()
         .map(|line| line.unwrap())
     )?;
 
-    let start = original_map.find_signed(b'S').unwrap_or_err()?;
-    let end = original_map.find_signed(b'E').unwrap_or_err()?;
-    original_map.signed_set(end.0, end.1, b'.');
-
-
-    let mut distance_field = HashMap::new();
-    distance_field.insert((end.0, end.1), 0);
-    let mut queue = VecDeque::new();
-    queue.push_back((end.0, end.1, 0));
-    while let Some((x, y, dist)) = queue.pop_front() {
-        for (dx, dy) in isize::DIRECTIONS {
-            let (nx, ny) = (x + dx, y + dy);
-            if distance_field.contains_key(&(nx, ny))
-                || original_map.signed_get_or_default(nx, ny) == b'#' {
-                continue;
-            }
-            distance_field.insert((nx, ny), dist + 1);
-            queue.push_back((nx, ny, dist + 1));
-        }
-    }
-    let uncheated_length = *distance_field
-        .get(&(start.0, start.1))
-        .unwrap_or_err()?;
-
-    let lengths = count_cheats(
-        &original_map,
-        (start.0, start.1),
-        2,
-        &distance_field,
-        uncheated_length as isize
-    );
+    let start = map.find_signed(b'S').unwrap_or_err()?;
+    let end = map.find_signed(b'E').unwrap_or_err()?;
+
+    let to_end = bfs([end], |pos| open_neighbours(&map, *pos, is_wall), |_| false).distances;
+    let from_start = bfs([start], |pos| open_neighbours(&map, *pos, is_wall), |_| false).distances;
+    let uncheated_length = *to_end
+        .get(&start)
+        .unwrap_or_err()? as isize;
+
+    let lengths = count_cheats(&from_start, &to_end, 2, uncheated_length);
     let good_cheats = lengths
         .iter()
-        .filter(|len| **len + 100 <= uncheated_length as isize)
+        .filter(|len| **len + saving <= uncheated_length)
         .count();
 
-    let lengths = count_cheats(
-        &original_map,
-        (start.0, start.1),
-        20,
-        &distance_field,
-        uncheated_length as isize
-    );
+    let lengths = count_cheats(&from_start, &to_end, 20, uncheated_length);
     let good_cheats2 = lengths
         .iter()
-        .filter(|len| **len + 100 <= uncheated_length as isize)
+        .filter(|len| **len + saving <= uncheated_length)
         .count();
 
 
     output!(good_cheats, good_cheats2)
 }
+
+
+#[test]
+fn test() {
+    use crate::misc::test::test_solver_with;
+
+    test_solver_with(solve, Params::new(PARAMS, &[("saving", "50")]).unwrap(), indoc::indoc! {"
+        ###############
+        #...#...#.....#
+        #.#.#.#.#.###.#
+        #S#...#.#.#...#
+        #######.#.#.###
+        #######.#.#...#
+        #######.#.###.#
+        ###..E#...#...#
+        ###.#######.###
+        #...###...#...#
+        #.#####.#.###.#
+        #.#...#.#.#...#
+        #.#.#.#.#.#.###
+        #...#...#...###
+        ###############
+    "}, output!(1, 285));
+}
@@ -1,6 +1,9 @@
 use std::{collections::HashMap, io::BufRead, iter::repeat};
 use color_eyre::eyre::{eyre, Result};
-use crate::{misc::{grid::Grid, option::OptionExt}, output, Input, Output};
+use crate::{misc::{grid::Grid, option::OptionExt}, output, register_day, Input, Output};
+
+
+register_day!(2024, 21, "Keypad Conundrum", solve);
 
 
 #[derive(Debug, Clone, Copy)]
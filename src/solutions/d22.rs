@@ -1,5 +1,11 @@
 use std::{collections::{hash_map::Entry, HashMap, HashSet}, io::BufRead};
-use crate::{misc::progress::pretty_progress_bar, output, Input, Output};
+use color_eyre::eyre::Result;
+use crate::{misc::progress::pretty_progress_bar, output, register_day, Input, PartOutput};
+
+
+register_day!(2024, 22, "Monkey Market", parse, part1, part2);
+
+const STEPS: usize = 2000;
 
 
 fn mix(num: i64, changed_num: i64) -> i64 {
@@ -21,23 +27,49 @@ fn next_num(num: i64) -> i64 {
     new
 }
 
-pub fn solve(input: Input) -> Output {
-    let steps = 2000;
+pub struct Buyers {
+    secrets: Vec<i64>
+}
+
+pub fn parse(input: Input) -> Result<Buyers> {
+    let mut secrets = Vec::new();
+    for line in input.lines() {
+        secrets.push(line?.parse()?);
+    }
+
+    Ok(Buyers {
+        secrets
+    })
+}
+
+pub fn part1(buyers: &Buyers) -> PartOutput {
+    let mut final_value_sum = 0;
+    for secret in &buyers.secrets {
+        let mut current = *secret;
+        for _ in 0..STEPS {
+            current = next_num(current);
+        }
+        final_value_sum += current;
+    }
+
+    output!(final_value_sum)
+}
+
+pub fn part2(buyers: &Buyers) -> PartOutput {
+    let steps = STEPS;
 
     let mut all_sequences = HashSet::new();
     let mut all_passed_sequences = Vec::new();
 
     let mut diff_history = Vec::with_capacity(steps - 1);
     let mut price_history = Vec::with_capacity(steps);
-    let mut final_value_sum = 0;
-    for line in input.lines() {
-        let line = line?;
+    for secret in &buyers.secrets {
         diff_history.clear();
         price_history.clear();
         let mut passed_sequences = HashMap::with_capacity(steps);
         let mut highest_diff_index = [None; 10];
 
-        let mut current = line.parse()?;
+        let mut current = *secret;
         let mut last_price = 0;
         for i in 0..steps {
             let price = current % 10;
@@ -62,7 +94,6 @@ pub fn solve(input: Input) -> Output {
 
             current = next_num(current);
         }
-        final_value_sum += current;
 
         all_passed_sequences.push(passed_sequences);
     }
@@ -84,5 +115,5 @@ pub fn solve(input: Input) -> Output {
         progress.inc(1);
     }
 
-    output!(final_value_sum, best_banana_winnings)
+    output!(best_banana_winnings)
 }
@@ -1,5 +1,8 @@
 use std::{collections::{HashMap, HashSet}, io::BufRead};
-use crate::{misc::option::OptionExt, output, Input, Output};
+use crate::{misc::option::OptionExt, output, register_day, Input, Output};
+
+
+register_day!(2024, 23, "LAN Party", solve);
 
 
 pub fn solve(input: Input) -> Output {
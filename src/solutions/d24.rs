@@ -1,9 +1,13 @@
 use std::{collections::{HashMap, HashSet, VecDeque}, io::BufRead};
 use color_eyre::eyre::Result;
 use itertools::Itertools;
+use log::debug;
 use regex::Regex;
 use regex_macro::regex;
-use crate::{misc::option::OptionExt, output, Input, Output};
+use crate::{misc::option::OptionExt, output, register_day, Input, PartOutput};
+
+
+register_day!(2024, 24, "Crossed Wires", parse, part1, part2);
 
 
 fn numbered_node_name(prefix: char, number: i32) -> String {
@@ -93,7 +97,7 @@ fn check_node_dependents(node: &String, typ: NodeType, dependents: &HashMap<Stri
             }
         }
         let Some((i, next_type)) = found else {
-            println!("Under {node} ({typ:?}) {dep} has incorrect connection {op:?}");
+            debug!(target: LOG_TARGET, "Under {node} ({typ:?}) {dep} has incorrect connection {op:?}");
             if to_swap.is_some() {
                 return true;
             }
@@ -134,7 +138,14 @@ fn check_node_dependencies(node: &String, typ: NodeType, dependencies: &HashMap<
 
 
 
-pub fn solve(input: Input) -> Output {
+pub struct Circuit {
+    variables: HashMap<String, i8>,
+    operations: Vec<String>,
+    dependents: HashMap<String, Vec<(String, String)>>,
+    output_bits: i32
+}
+
+pub fn parse(input: Input) -> Result<Circuit> {
     let input_regex = regex!(r"([\w\d]+): (\d)");
     let operation_regex = regex!(r"([\w\d]+) (\w+) ([\w\d]+) -> ([\w\d]+)");
     let mut lines = input.lines();
@@ -155,13 +166,13 @@ pub fn solve(input: Input) -> Output {
     let mut dependencies = HashMap::<String, Vec<(String, Vec<String>)>>::new();
     let mut dependents = HashMap::<String, Vec<(String, String)>>::new();
 
-    let mut queue = VecDeque::new();
+    let mut operations = Vec::new();
     for line in lines {
         let line = line?;
         if line.is_empty() {
             break;
         }
-        queue.push_back(line.clone());
+        operations.push(line.clone());
 
         let captures = operation_regex.captures(line.as_str())
             .unwrap_or_err()?;
@@ -177,11 +188,6 @@ pub fn solve(input: Input) -> Output {
                 .push((captures[2].to_string(), captures[4].to_string()));
         }
     }
-    while let Some(current) = queue.pop_front() {
-        if operate(operation_regex, current.as_str(), &mut variables).is_err() {
-            queue.push_back(current);
-        };
-    }
 
     // graphviz:
     // for (dest, (dep, op)) in &dependencies {
@@ -190,6 +196,32 @@ pub fn solve(input: Input) -> Output {
     //     }
     // }
 
+    let output_bits = dependencies
+        .keys()
+        .filter(|node| node.starts_with('z'))
+        .count() as i32;
+
+    Ok(Circuit {
+        variables,
+        operations,
+        dependents,
+        output_bits
+    })
+}
+
+pub fn part1(circuit: &Circuit) -> PartOutput {
+    let operation_regex = regex!(r"([\w\d]+) (\w+) ([\w\d]+) -> ([\w\d]+)");
+    let mut variables = circuit.variables.clone();
+
+    let mut queue = circuit.operations
+        .iter()
+        .collect::<VecDeque<_>>();
+    while let Some(current) = queue.pop_front() {
+        if operate(operation_regex, current.as_str(), &mut variables).is_err() {
+            queue.push_back(current);
+        };
+    }
+
     let mut output = 0i64;
     let mut i = 0;
     while let Some(bit) = variables.get(numbered_node_name('z', i).as_str()) {
@@ -197,25 +229,29 @@ pub fn solve(input: Input) -> Output {
         i += 1;
     }
 
+    output!(output)
+}
+
+pub fn part2(circuit: &Circuit) -> PartOutput {
     let mut swaps = HashSet::new();
-    for i in 2..i-1 {
-        debug_assert!(!check_node_dependents(&numbered_node_name('x', i), NodeType::Input, &dependents, &mut swaps));
-        debug_assert!(!check_node_dependents(&numbered_node_name('y', i), NodeType::Input, &dependents, &mut swaps));
+    for i in 2..circuit.output_bits-1 {
+        debug_assert!(!check_node_dependents(&numbered_node_name('x', i), NodeType::Input, &circuit.dependents, &mut swaps));
+        debug_assert!(!check_node_dependents(&numbered_node_name('y', i), NodeType::Input, &circuit.dependents, &mut swaps));
     }
 
     // remove last carry node that starts with a 'z'
     swaps.remove(&numbered_node_name('z', 45));
 
     debug_assert_eq!(swaps.len(), 8, "{swaps:?}");
-    output!(output, swaps.iter().sorted().map(|s| s.as_str()).collect::<Vec<_>>().join(","))
+    output!(swaps.iter().sorted().map(|s| s.as_str()).collect::<Vec<_>>().join(","))
 }
 
 
 #[test]
 fn test() {
-    use crate::misc::test::test_solver;
+    use crate::misc::test::test_part;
 
-    test_solver(solve, indoc::indoc! {"
+    test_part(parse, part1, indoc::indoc! {"
         x00: 1
         x01: 0
         x02: 1
@@ -1,9 +1,20 @@
 use std::io::BufRead;
+use color_eyre::eyre::Result;
 use itertools::Itertools;
+use log::trace;
+use crate::{misc::grid::Grid, output, register_day, Input, PartOutput};
 
-use crate::{misc::grid::Grid, output, Input, Output};
 
-pub fn solve(input: Input) -> Output {
+register_day!(2024, 25, "Code Chronicle", parse, part1);
+
+
+pub struct Schematics {
+    keys: Vec<Vec<usize>>,
+    locks: Vec<Vec<usize>>,
+    max_depth: usize
+}
+
+pub fn parse(input: Input) -> Result<Schematics> {
     let mut keys = Vec::new();
     let mut locks = Vec::new();
     let mut max_depth = 0;
@@ -55,15 +66,25 @@ pub fn solve(input: Input) -> Output {
         }).push(depths);
     }
 
+    Ok(Schematics {
+        keys,
+        locks,
+        max_depth
+    })
+}
+
+pub fn part1(schematics: &Schematics) -> PartOutput {
+    let Schematics { keys, locks, max_depth } = schematics;
+
     let mut count = 0;
     for key in keys {
-        'locks: for lock in &locks {
+        'locks: for lock in locks {
             for (key_depth, lock_depth) in key.iter().zip(lock.iter()) {
-                if key_depth + lock_depth > max_depth {
+                if key_depth + lock_depth > *max_depth {
                     continue 'locks;
                 }
             }
-            println!("{key:?} {lock:?}");
+            trace!(target: LOG_TARGET, "Key {key:?} fits lock {lock:?}");
             count += 1;
         }
     }
@@ -74,9 +95,9 @@ pub fn solve(input: Input) -> Output {
 
 #[test]
 fn test() {
-    use crate::misc::test::test_solver;
+    use crate::misc::test::test_part;
 
-    test_solver(solve, indoc::indoc! {"
+    test_part(parse, part1, indoc::indoc! {"
         #####
         .####
         .####
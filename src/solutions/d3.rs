@@ -1,6 +1,9 @@
 use std::io::BufRead;
 use regex_macro::regex;
-use crate::{output, Input, Output};
+use crate::{output, register_day, Input, Output};
+
+
+register_day!(2024, 3, "Mull It Over", solve);
 
 
 pub fn solve(input: Input) -> Output {
@@ -1,5 +1,8 @@
 use std::io::BufRead;
-use crate::{misc::{grid::Grid, vector2::Directions}, output, Input, Output};
+use crate::{misc::{grid::Grid, vector2::Directions}, output, register_day, Input, Output};
+
+
+register_day!(2024, 4, "Ceres Search", solve);
 
 
 pub fn solve(input: Input) -> Output {
@@ -1,5 +1,8 @@
 use std::{collections::HashMap, io::BufRead};
-use crate::{misc::option::OptionExt, output, Input, Output};
+use crate::{misc::option::OptionExt, output, register_day, Input, Output};
+
+
+register_day!(2024, 5, "Print Queue", solve);
 
 
 fn has_incorrect_dependencies(manual: impl AsRef<[i32]>, dependencies: &HashMap<i32, Vec<i32>>) -> Option<usize> {
@@ -1,5 +1,9 @@
 use std::{collections::HashMap, io::BufRead, ops::Rem};
-use crate::{misc::{grid::Grid, option::OptionExt, progress::pretty_progress_bar, vector2::Directions}, output, Input, Output};
+use color_eyre::eyre::Result;
+use crate::{misc::{grid::Grid, option::OptionExt, progress::pretty_progress_bar, vector2::Directions}, output, register_day, Input, PartOutput};
+
+
+register_day!(2024, 6, "Guard Gallivant", parse, part1, part2);
 
 
 /// Returns whether the path loops.
@@ -43,27 +47,52 @@ fn traverse(map: &mut Grid, mut x: isize, mut y: isize) -> bool {
     false
 }
 
-pub fn solve(input: Input) -> Output {
-    let original_map = Grid::from(input
+pub struct Lab {
+    map: Grid,
+    guard: (isize, isize)
+}
+
+pub fn parse(input: Input) -> Result<Lab> {
+    let map = Grid::from(input
         .lines()
         .map(|line| line.unwrap())
     )?;
-    let (ux, uy) = original_map.find_signed(b'^').unwrap_or_err()?;
+    let guard = map.find_signed(b'^').unwrap_or_err()?;
+
+    Ok(Lab {
+        map,
+        guard
+    })
+}
 
-    let mut map = original_map.clone();
-    assert!(!traverse(&mut map, ux, uy));
+/// Gets the map with the path the guard walks marked with 'X'.
+fn patrol(lab: &Lab) -> Grid {
+    let mut map = lab.map.clone();
+    assert!(!traverse(&mut map, lab.guard.0, lab.guard.1));
+    map
+}
 
-    let count = map
+pub fn part1(lab: &Lab) -> PartOutput {
+    let count = patrol(lab)
         .iter()
         .filter(|(_, _, value)| value == &b'X')
         .count();
 
+    output!(count)
+}
 
-    let progress = pretty_progress_bar(count as u64);
+pub fn part2(lab: &Lab) -> PartOutput {
+    let (ux, uy) = lab.guard;
+    let map = patrol(lab);
+    let path = map
+        .iter_signed()
+        .filter(|(_, _, value)| value == &b'X');
+
+    let progress = pretty_progress_bar(path.clone().count() as u64);
 
     let mut obstruction_count = 0;
-    for (x, y, _) in map.iter_signed().filter(|(_, _, value)| value == &b'X') {
-        let mut map = original_map.clone();
+    for (x, y, _) in path {
+        let mut map = lab.map.clone();
         map.signed_set(x, y, b'O');
         if traverse(&mut map, ux, uy) {
             obstruction_count += 1;
@@ -71,24 +100,13 @@ pub fn solve(input: Input) -> Output {
         progress.inc(1);
     }
 
-    output!(count, obstruction_count)
+    output!(obstruction_count)
 }
 
 
 #[test]
 fn test() {
-    use crate::misc::test::test_solver;
-
-    test_solver(solve, indoc::indoc! {"
-        ....#.....
-        .........#
-        ..........
-        ..#.......
-        .......#..
-        ..........
-        .#..^.....
-        ........#.
-        #.........
-        ......#...
-    "}, output!(41, 6));
+    use crate::misc::test::test_examples;
+
+    test_examples(2024, 6);
 }
@@ -1,6 +1,9 @@
 use std::io::BufRead;
 use itertools::Itertools;
-use crate::{misc::grid::Grid, output, Input, Output};
+use crate::{misc::grid::Grid, output, register_day, Input, Output};
+
+
+register_day!(2024, 8, "Resonant Collinearity", solve);
 
 
 pub fn solve(input: Input) -> Output {
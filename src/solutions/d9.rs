@@ -1,5 +1,8 @@
 use std::io::BufRead;
-use crate::{misc::option::OptionExt, output, Input, Output};
+use crate::{misc::option::OptionExt, output, register_day, Input, Output};
+
+
+register_day!(2024, 9, "Disk Fragmenter", solve);
 
 
 fn shrink_1(blocks: &mut Vec<Option<u32>>) -> usize {
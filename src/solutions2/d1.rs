@@ -1,5 +1,9 @@
 use std::io::BufRead;
-use crate::{Input, Output, output};
+use crate::{Input, Output, output, register_day};
+
+
+register_day!(2025, 1, "Secret Entrance", solve);
+
 
 pub fn solve(input: Input) -> Output {
     let mut clicks = Vec::<i32>::new();
@@ -1,7 +1,10 @@
 use std::{collections::{VecDeque}, io::BufRead};
 use color_eyre::eyre::Result;
 use good_lp::{Expression, Solution, SolverModel, default_solver, variable, variables};
-use crate::{Input, Output, misc::{option::OptionExt, progress::pretty_progress_bar}, output};
+use crate::{Input, Output, misc::{option::OptionExt, progress::pretty_progress_bar}, output, register_day};
+
+
+register_day!(2025, 10, "Factory", solve);
 
 
 #[derive(Debug, Default, Hash)]
@@ -1,5 +1,8 @@
 use std::{collections::{HashMap, HashSet, VecDeque}, io::BufRead};
-use crate::{Input, Output, misc::option::OptionExt, output};
+use crate::{Input, Output, misc::option::OptionExt, output, register_day};
+
+
+register_day!(2025, 11, "Reactor", solve);
 
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -33,7 +36,8 @@ pub fn solve(input: Input) -> Output {
     let mut total_options = 0i64;
 
     while let Some((current, options)) = queue.pop_front() {
-        for next in connections[current].iter() {
+        // the examples of each part lack the start of the other
+        for next in connections.get(current).into_iter().flatten() {
             if *next == "out" {
                 total_options += options;
                 continue;
@@ -68,7 +72,7 @@ pub fn solve(input: Input) -> Output {
     let mut valid_server_options = 0;
 
     while let Some((current, visiteds)) = queue.pop_front() {
-        for next in connections[current].iter() {
+        for next in connections.get(current).into_iter().flatten() {
             if *next == "out" {
                 for visited in visiteds.iter() {
                     if visited.dac && visited.fft {
@@ -127,35 +131,7 @@ pub fn solve(input: Input) -> Output {
 
 #[test]
 fn test() {
-    use crate::misc::test::test_solver;
-
-    // test_solver(solve, indoc::indoc! {"
-    //     aaa: you hhh
-    //     you: bbb ccc
-    //     bbb: ddd eee
-    //     ccc: ddd eee fff
-    //     ddd: ggg
-    //     eee: out
-    //     fff: out
-    //     ggg: out
-    //     hhh: ccc fff iii
-    //     iii: out
-    // "}, output!(5));
-
-    test_solver(solve, indoc::indoc! {"
-        svr: aaa bbb
-        you: bbb ccc
-        aaa: fft
-        fft: ccc
-        bbb: tty
-        tty: ccc
-        ccc: ddd eee
-        ddd: hub
-        hub: fff
-        eee: dac
-        dac: fff
-        fff: ggg hhh
-        ggg: out
-        hhh: out
-    "}, output!(4, 2));
+    use crate::misc::test::test_examples;
+
+    test_examples(2025, 11);
 }
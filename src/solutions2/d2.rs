@@ -1,5 +1,9 @@
 use std::io::BufRead;
-use crate::{Input, Output, output};
+use crate::{Input, Output, output, register_day};
+
+
+register_day!(2025, 2, "Gift Shop", solve);
+
 
 fn has_repeats(input: &[u8]) -> bool {
     for i in 1..(input.len() / 2 + 1) {
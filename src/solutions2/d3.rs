@@ -1,5 +1,8 @@
 use std::{collections::HashMap, io::BufRead};
-use crate::{Input, Output, output, misc::progress::pretty_progress_bar};
+use crate::{Input, Output, output, register_day, misc::progress::pretty_progress_bar};
+
+
+register_day!(2025, 3, "Lobby", solve);
 
 
 fn local_max<'a>(line: &'a [u8], remaining_depth: u32, cache: &mut HashMap<(&'a [u8], u32), i64>) -> i64 {
@@ -1,12 +1,16 @@
 use std::io::BufRead;
-use crate::{Input, Output, output, misc::{grid::Grid, vector2::Directions}};
+use crate::{Input, Output, output, register_day, misc::grid::Grid};
+
+
+register_day!(2025, 4, "Printing Department", solve);
+
 
 fn get_forklift_positions(grid: &Grid) -> Vec<(usize, usize)> {
     let mut positions = Vec::new();
     for (x, y, c) in grid.iter() {
         if c == b'@' {
-            let count = isize::DIAGONAL_DIRECTIONS.iter()
-                .filter(|(dx, dy)| grid.signed_get(x as isize + *dx, y as isize + *dy) == Some(b'@'))
+            let count = grid.diagonal_neighbours(x as isize, y as isize)
+                .filter(|(_, _, _, c)| *c == b'@')
                 .count();
 
             if count < 4 {
@@ -1,5 +1,9 @@
 use std::{cmp::{max, min}, io::BufRead, ops::RangeInclusive};
-use crate::{Input, Output, output, misc::option::OptionExt};
+use crate::{Input, Output, output, register_day, misc::option::OptionExt};
+
+
+register_day!(2025, 5, "Cafeteria", solve);
+
 
 pub fn solve(input: Input) -> Output {
     let mut lines = input.lines();
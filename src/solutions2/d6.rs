@@ -1,5 +1,9 @@
 use std::io::{BufRead};
-use crate::{Input, Output, output};
+use crate::{Input, Output, output, register_day};
+
+
+register_day!(2025, 6, "Trash Compactor", solve);
+
 
 pub fn solve(input: Input) -> Output {
     let lines: Vec<String> = input.lines().collect::<Result<Vec<_>, _>>()?;
@@ -1,5 +1,9 @@
 use std::{collections::HashMap, io::BufRead};
-use crate::{Input, Output, misc::{grid::Grid, option::OptionExt}, output};
+use crate::{Input, Output, misc::{grid::Grid, option::OptionExt}, output, register_day};
+
+
+register_day!(2025, 7, "Laboratories", solve);
+
 
 pub fn solve(input: Input) -> Output {
     let mut grid = Grid::from(input
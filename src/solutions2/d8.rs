@@ -1,7 +1,14 @@
 use std::{collections::HashSet, i64::MAX, io::BufRead};
 use color_eyre::eyre::Result;
 use itertools::Itertools;
-use crate::{Input, Output, misc::{option::OptionExt, progress::pretty_progress_bar}, output};
+use crate::{Input, Output, misc::{option::OptionExt, progress::pretty_progress_bar}, output, params::{Param, Params}, register_day};
+
+
+register_day!(2025, 8, "Playground", params = PARAMS, solve);
+
+const PARAMS: &[Param] = &[
+    Param { name: "connections", default: "1000", help: "Number of closest pairs connected in part 1" }
+];
 
 
 type Coord = (i64, i64, i64);
@@ -95,7 +102,7 @@ fn find_connections(junctions: &[Coord]) -> Result<(Coord, Coord)> {
     Ok((min_a, min_b))
 }
 
-pub fn solve(input: Input) -> Output {
+pub fn solve(input: Input, params: &Params) -> Output {
     let mut positions = Vec::new();
 
     for line in input.lines() {
@@ -107,7 +114,7 @@ pub fn solve(input: Input) -> Output {
         }
     }
 
-    let circuits = make_connections(&positions, positions.len())?;
+    let circuits = make_connections(&positions, params.get("connections")?)?;
 
     let last_connection = find_connections(&positions)?;
 
@@ -120,7 +127,7 @@ pub fn solve(input: Input) -> Output {
 
 #[test]
 fn test() {
-    use crate::misc::test::test_solver;
+    use crate::misc::test::test_solver_with;
 
     assert_eq!(make_connections(&[
         (162,817,812),
@@ -150,7 +157,7 @@ fn test() {
         vec![5, 4, 2]
     );
 
-    test_solver(solve, indoc::indoc! {"
+    test_solver_with(solve, Params::new(PARAMS, &[("connections", "10")]).unwrap(), indoc::indoc! {"
         162,817,812
         57,618,57
         906,360,560
@@ -171,5 +178,5 @@ fn test() {
         862,61,35
         984,92,344
         425,690,689
-    "}, output!(45, 25272));
+    "}, output!(40, 25272));
 }
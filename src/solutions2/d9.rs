@@ -1,5 +1,9 @@
 use std::{cmp::{min, max}, io::BufRead};
-use crate::{Input, Output, misc::{option::OptionExt, progress::pretty_progress_bar}, output};
+use crate::{Input, Output, misc::{option::OptionExt, progress::pretty_progress_bar}, output, register_day};
+
+
+register_day!(2025, 9, "Movie Theater", solve);
+
 
 pub fn solve(input: Input) -> Output {
     let mut red_corners = Vec::new();
@@ -1,21 +1 @@
-use crate::{Input, Output};
-
 automod::dir!("src/solutions2");
-
-
-pub fn solve_day(day: u32, input: Input) -> Option<Output> {
-    match day {
-        1 => Some(d1::solve(input)),
-        2 => Some(d2::solve(input)),
-        3 => Some(d3::solve(input)),
-        4 => Some(d4::solve(input)),
-        5 => Some(d5::solve(input)),
-        6 => Some(d6::solve(input)),
-        7 => Some(d7::solve(input)),
-        8 => Some(d8::solve(input)),
-        9 => Some(d9::solve(input)),
-        10 => Some(d10::solve(input)),
-        11 => Some(d11::solve(input)),
-        _ => None
-    }
-}
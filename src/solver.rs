@@ -0,0 +1,215 @@
+use std::{any::Any, fmt::{self, Display}, path::Path};
+use color_eyre::eyre::Result;
+use crate::{params::{Param, Params}, Input, PartOutput};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn from_number(part: u8) -> Option<Self> {
+        match part {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None
+        }
+    }
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.number().fmt(f)
+    }
+}
+
+
+/// Parsed input shared by the parts of a solver.
+pub type Parsed = Box<dyn Any>;
+
+/// A solution to a single puzzle.
+///
+/// Implemented by day files through [`register_day!`](crate::register_day),
+/// which also adds it to the registry.
+pub trait Solver: Sync {
+    fn year(&self) -> i32;
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+    /// File the solver is in, relative to the crate root.
+    fn source_file(&self) -> &'static str;
+
+    /// Whether the parts are solved separately,
+    /// otherwise both are already solved by [`Solver::parse`].
+    fn has_separate_parts(&self) -> bool;
+
+    /// Parameters the solver takes, with the values of the real puzzle as defaults.
+    fn params(&self) -> &'static [Param];
+
+    /// Parses the input into the state shared by both parts.
+    fn parse(&self, input: Input, params: &Params) -> Result<Parsed>;
+
+    /// Returns `None` if the solver doesn't have the given part.
+    fn solve_part(&self, parsed: &Parsed, part: Part) -> Option<PartOutput>;
+}
+
+pub struct Registration(pub &'static dyn Solver);
+inventory::collect!(Registration);
+
+/// Implements [`Solver`] for a day and registers it.
+///
+/// Either with a single function solving both parts at once,
+/// `register_day!(year, day, title, solve)`,
+/// or with a parse function and a function per part,
+/// `register_day!(year, day, title, parse, part1, part2)`, where part 2 may be left out.
+///
+/// Days with parameters declare them after the title, `register_day!(year, day, title, params = PARAMS, ...)`,
+/// their solve or parse function then also takes the [`Params`](crate::params::Params).
+///
+/// The day's module gets a `LOG_TARGET` to log its diagnostics with, see [`logging`](crate::logging).
+#[macro_export]
+macro_rules! register_day {
+    ($year:expr, $day:expr, $title:expr, params = $params:expr, $solve:path) => {
+        $crate::register_day!(@combined $year, $day, $title, $params, |input, params| Ok(Box::new($solve(input, params)?)));
+    };
+    ($year:expr, $day:expr, $title:expr, params = $params:expr, $parse:path, $part1:path, $part2:path) => {
+        $crate::register_day!(@separate $year, $day, $title, $params, |input, params| Ok(Box::new($parse(input, params)?)), $part1, $part2);
+    };
+    ($year:expr, $day:expr, $title:expr, $solve:path) => {
+        $crate::register_day!(@combined $year, $day, $title, &[], |input, _| Ok(Box::new($solve(input)?)));
+    };
+    ($year:expr, $day:expr, $title:expr, $parse:path, $part1:path) => {
+        $crate::register_day!(@impl $year, $day, $title, &[], true,
+            |input, _| Ok(Box::new($parse(input)?)),
+            |parsed, part| match part {
+                $crate::solver::Part::One => Some($part1(parsed
+                    .downcast_ref()
+                    .expect("Parsed input has a different type than part 1 expects"))),
+                $crate::solver::Part::Two => None
+            }
+        );
+    };
+    ($year:expr, $day:expr, $title:expr, $parse:path, $part1:path, $part2:path) => {
+        $crate::register_day!(@separate $year, $day, $title, &[], |input, _| Ok(Box::new($parse(input)?)), $part1, $part2);
+    };
+    (@combined $year:expr, $day:expr, $title:expr, $params:expr, $parse:expr) => {
+        $crate::register_day!(@impl $year, $day, $title, $params, false,
+            $parse,
+            |parsed, part| {
+                let (part1, part2): &($crate::misc::output::OutputValue, $crate::misc::output::OutputValue) = parsed
+                    .downcast_ref()
+                    .expect("Parsed input is not the output of solve");
+                Some(Ok(match part {
+                    $crate::solver::Part::One => part1.clone(),
+                    $crate::solver::Part::Two => part2.clone()
+                }))
+            }
+        );
+    };
+    (@separate $year:expr, $day:expr, $title:expr, $params:expr, $parse:expr, $part1:path, $part2:path) => {
+        $crate::register_day!(@impl $year, $day, $title, $params, true,
+            $parse,
+            |parsed, part| match part {
+                $crate::solver::Part::One => Some($part1(parsed
+                    .downcast_ref()
+                    .expect("Parsed input has a different type than part 1 expects"))),
+                $crate::solver::Part::Two => Some($part2(parsed
+                    .downcast_ref()
+                    .expect("Parsed input has a different type than part 2 expects")))
+            }
+        );
+    };
+    (@impl $year:expr, $day:expr, $title:expr, $params:expr, $separate:expr, $parse:expr, $solve_part:expr) => {
+        struct DaySolver;
+
+        /// Target of the day's log messages, like `2024::d14`.
+        #[allow(dead_code)]
+        const LOG_TARGET: &str = concat!($year, "::d", $day);
+
+        impl $crate::solver::Solver for DaySolver {
+            fn year(&self) -> i32 {
+                $year
+            }
+            fn day(&self) -> u32 {
+                $day
+            }
+            fn title(&self) -> &'static str {
+                $title
+            }
+            fn source_file(&self) -> &'static str {
+                file!()
+            }
+            fn has_separate_parts(&self) -> bool {
+                $separate
+            }
+            fn params(&self) -> &'static [$crate::params::Param] {
+                $params
+            }
+            fn parse(&self, input: $crate::Input, params: &$crate::params::Params) -> color_eyre::eyre::Result<$crate::solver::Parsed> {
+                let parse: fn($crate::Input, &$crate::params::Params) -> color_eyre::eyre::Result<$crate::solver::Parsed> = $parse;
+                parse(input, params)
+            }
+            fn solve_part(&self, parsed: &$crate::solver::Parsed, part: $crate::solver::Part) -> Option<$crate::PartOutput> {
+                let solve_part: fn(&$crate::solver::Parsed, $crate::solver::Part) -> Option<$crate::PartOutput> = $solve_part;
+                solve_part(parsed, part)
+            }
+        }
+
+        inventory::submit!($crate::solver::Registration(&DaySolver));
+    };
+}
+
+
+/// All registered solvers, ordered by year and day.
+pub fn all() -> Vec<&'static dyn Solver> {
+    let mut solvers = inventory::iter::<Registration>
+        .into_iter()
+        .map(|registration| registration.0)
+        .collect::<Vec<_>>();
+    solvers.sort_by_key(|solver| (solver.year(), solver.day()));
+    solvers
+}
+
+/// Registered solvers of the given year and day, every year or day if not given.
+pub fn select(year: Option<i32>, day: Option<u32>) -> Vec<&'static dyn Solver> {
+    all()
+        .into_iter()
+        .filter(|solver| year.is_none_or(|year| year == solver.year())
+            && day.is_none_or(|day| day == solver.day()))
+        .collect()
+}
+
+pub fn find(year: i32, day: u32) -> Option<&'static dyn Solver> {
+    all()
+        .into_iter()
+        .find(|solver| solver.year() == year && solver.day() == day)
+}
+
+/// Directory of the day files of the given year, relative to the crate root.
+/// `None` if the year has no solvers yet.
+pub fn year_dir(year: i32) -> Option<&'static Path> {
+    all()
+        .into_iter()
+        .find(|solver| solver.year() == year)
+        .and_then(|solver| Path::new(solver.source_file()).parent())
+}
+
+/// Years that have at least one solver, oldest first.
+pub fn years() -> Vec<i32> {
+    let mut years = all()
+        .iter()
+        .map(|solver| solver.year())
+        .collect::<Vec<_>>();
+    years.dedup();
+    years
+}
@@ -0,0 +1,252 @@
+use std::{fs, path::{Path, PathBuf}};
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{bail, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use crate::{answers::Answers, cli::{Exit, Failure}, client::{find_session, Client}, misc::{output::OutputValue, toml_file}, report::{ErrorKind, ErrorReport}, runner::{DayRun, PartRun, RunOutcome}, solver::Part};
+
+
+/// How the server judged a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Response {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Answered too recently, the answer was not judged.
+    RateLimited,
+    /// The part was already solved, or isn't unlocked yet.
+    WrongLevel,
+    Unknown
+}
+
+impl Response {
+    /// Reads the response from the page the server returned.
+    pub fn parse(page: &str) -> Self {
+        if page.contains("That's the right answer") {
+            Self::Correct
+        } else if page.contains("You gave an answer too recently") {
+            Self::RateLimited
+        } else if page.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else if page.contains("your answer is too high") {
+            Self::TooHigh
+        } else if page.contains("your answer is too low") {
+            Self::TooLow
+        } else if page.contains("That's not the right answer") {
+            Self::Wrong
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Whether the answer is known to be wrong.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Self::Wrong | Self::TooHigh | Self::TooLow)
+    }
+}
+
+/// Text of the main article of a page, without its tags.
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map(|(article, _)| article.split_once('>').map_or(article, |(_, text)| text))
+        .unwrap_or(page);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => ()
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    pub year: i32,
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    pub response: Response,
+    pub time: DateTime<Utc>
+}
+
+/// Every answer that was submitted, stored as `{answers}/submissions.toml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubmissionLog {
+    #[serde(default, rename = "submission")]
+    submissions: Vec<Submission>
+}
+
+pub fn log_path(answers: &Path) -> PathBuf {
+    answers.join("submissions.toml")
+}
+
+impl SubmissionLog {
+    pub fn load(answers: &Path) -> Result<Self> {
+        toml_file::load_or_default(&log_path(answers), "submission log")
+    }
+
+    pub fn save(&self, answers: &Path) -> Result<()> {
+        let path = log_path(answers);
+        fs::create_dir_all(answers)?;
+        fs::write(&path, toml::to_string(self)?)
+            .wrap_err_with(|| format!("Could not write {}", path.display()))
+    }
+
+    pub fn push(&mut self, submission: Submission) {
+        self.submissions.push(submission);
+    }
+
+    fn of_part(&self, year: i32, day: u32, part: Part) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |submission| submission.year == year && submission.day == day && submission.part == part.number())
+    }
+
+    /// The exclusive lower and upper bounds of the answer, learned from answers that were too low or too high.
+    pub fn bounds(&self, year: i32, day: u32, part: Part) -> (Option<i128>, Option<i128>) {
+        let mut lower = None;
+        let mut upper = None;
+        for submission in self.of_part(year, day, part) {
+            let Ok(answer) = submission.answer.parse::<i128>() else {
+                continue;
+            };
+            match submission.response {
+                Response::TooLow => lower = lower.max(Some(answer)),
+                Response::TooHigh => upper = Some(upper.map_or(answer, |upper: i128| upper.min(answer))),
+                _ => ()
+            }
+        }
+        (lower, upper)
+    }
+
+    /// Fails if the answer is already known to be wrong, outside the known bounds, or the part is already solved.
+    pub fn check(&self, year: i32, day: u32, part: Part, answer: &str) -> Result<()> {
+        for submission in self.of_part(year, day, part) {
+            if submission.response == Response::Correct {
+                bail!("Part {part} was already solved with {}", submission.answer);
+            }
+            if submission.answer == answer && submission.response.is_wrong() {
+                bail!("{answer} was already submitted at {} and is wrong", submission.time);
+            }
+        }
+
+        let Ok(number) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        match self.bounds(year, day, part) {
+            (Some(lower), _) if number <= lower => bail!("{answer} is too low, answer must be > {lower}"),
+            (_, Some(upper)) if number >= upper => bail!("{answer} is too high, answer must be < {upper}"),
+            _ => Ok(())
+        }
+    }
+}
+
+
+#[derive(Debug)]
+pub struct Reply {
+    pub response: Response,
+    /// What the server said, without markup.
+    pub message: String,
+    pub bounds: (Option<i128>, Option<i128>)
+}
+
+impl Reply {
+    /// Code to exit with when the answer wasn't correct, [`Exit::Mismatch`] when it was judged wrong.
+    pub fn exit_code(&self) -> Option<Exit> {
+        match self.response {
+            Response::Correct => None,
+            response if response.is_wrong() => Some(Exit::Mismatch),
+            _ => Some(Exit::Failure)
+        }
+    }
+}
+
+
+/// The answer of `part` in the run of a day, to submit it.
+pub fn part_answer(run: &DayRun, part: Part) -> Result<&OutputValue, Failure> {
+    let error = match (&run.outcome, run.part(part)) {
+        (_, Some(PartRun { result: Ok(answer), .. })) => return Ok(answer),
+        (RunOutcome::Failed(err), _) => Failure::new(Exit::SolverError, ErrorReport::from_report(ErrorKind::Parse, err)),
+        (_, Some(PartRun { result: Err(err), .. })) => Failure::new(Exit::SolverError, ErrorReport::from_report(ErrorKind::Part, err)),
+        (RunOutcome::TimedOut(timeout), _) => Failure::new(Exit::SolverError, ErrorReport::new(ErrorKind::TimedOut, format!("The solver {timeout}"))),
+        _ => Failure::new(Exit::UnknownPuzzle, ErrorReport::new(ErrorKind::NoSolution, format!("Given day has no part {part}")))
+    };
+    Err(error.of_puzzle(run.year, run.day))
+}
+
+/// Submits an answer unless the submission log already knows it is wrong,
+/// logging the response and storing correct answers in the answers directory.
+pub fn submit(base_url: &str, session: Option<&str>, answers: &Path, year: i32, day: u32, part: Part, answer: &OutputValue) -> Result<Reply> {
+    let answer = answer.to_string();
+    let mut log = SubmissionLog::load(answers)?;
+    log.check(year, day, part, &answer)?;
+
+    let client = Client::new(base_url, &find_session(session)?);
+    let page = client.submit_answer(year, day, part, &answer)?;
+    let response = Response::parse(&page);
+
+    log.push(Submission {
+        year,
+        day,
+        part: part.number(),
+        answer: answer.clone(),
+        response,
+        time: Utc::now()
+    });
+    log.save(answers)?;
+
+    if response == Response::Correct {
+        let mut stored = Answers::load(answers, year)?;
+        stored.set(day, part, answer);
+        stored.save(answers, year)?;
+    }
+
+    Ok(Reply {
+        response,
+        message: article_text(&page),
+        bounds: log.bounds(year, day, part)
+    })
+}
+
+
+#[test]
+fn test() {
+    use crate::misc::test::{mock_server, temp_dir};
+
+    let page = |text: &str| format!("<html><main><article><p>{text}</p></article></main></html>");
+    let (url, requests) = mock_server(move |request| match request.body.as_str() {
+        "level=1&answer=500" => (200, page("That's not the right answer; your answer is too high.")),
+        "level=1&answer=100" => (200, page("That's the right answer! You are <em>one gold star</em> closer.")),
+        _ => (200, page("You gave an answer too recently; you have to wait after submitting an answer."))
+    });
+    let answers = temp_dir("submit");
+    let submit = |answer: i32| submit(&url, Some("token"), &answers, 2024, 1, Part::One, &OutputValue::from(answer));
+
+    let reply = submit(500).unwrap();
+    assert_eq!(reply.response, Response::TooHigh);
+    assert_eq!(reply.message, "That's not the right answer; your answer is too high.");
+    assert_eq!(reply.bounds, (None, Some(500)));
+    assert_eq!(reply.exit_code(), Some(Exit::Mismatch));
+
+    assert!(submit(500).is_err(), "Already known to be wrong");
+    assert!(submit(600).unwrap_err().to_string().contains("answer must be < 500"));
+    assert_eq!(submit(200).unwrap().exit_code(), Some(Exit::Failure));
+    let correct = submit(100).unwrap();
+    assert_eq!((correct.response, correct.exit_code()), (Response::Correct, None));
+    assert!(submit(150).is_err(), "Already solved");
+
+    assert_eq!(requests.lock().unwrap().len(), 3);
+    assert_eq!(SubmissionLog::load(&answers).unwrap().submissions.len(), 3);
+    assert_eq!(Answers::load(&answers, 2024).unwrap().get(1, Part::One), Some("100"));
+
+    fs::remove_dir_all(answers).unwrap();
+}
@@ -0,0 +1,65 @@
+use std::{fs, panic::{self, AssertUnwindSafe}, path::{Path, PathBuf}, thread, time::{Duration, SystemTime}};
+
+
+/// Time between checking the watched files for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// Time the files must stay the same before running, so a save that writes in several steps runs once.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+
+/// Modification time and size of a file, `None` while it doesn't exist.
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamps(files: &[PathBuf]) -> Vec<Stamp> {
+    files
+        .iter()
+        .map(|file| {
+            let metadata = fs::metadata(file).ok()?;
+            Some((metadata.modified().ok()?, metadata.len()))
+        })
+        .collect()
+}
+
+/// Waits until one of the files changes, and then until they stop changing.
+fn wait_for_change(files: &[PathBuf], last: &mut Vec<Stamp>) {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = stamps(files);
+        if current != *last {
+            *last = current;
+            break;
+        }
+    }
+
+    loop {
+        thread::sleep(DEBOUNCE);
+        let current = stamps(files);
+        if current == *last {
+            return;
+        }
+        *last = current;
+    }
+}
+
+/// Runs `run` on each of the files, then again on all of them every time one of them changes, until the process is stopped.
+///
+/// A panic in `run` is reported by the panic hook, after which the files are watched again.
+pub fn watch(files: &[PathBuf], mut run: impl FnMut(&Path)) -> ! {
+    let mut last = stamps(files);
+    loop {
+        for file in files {
+            if panic::catch_unwind(AssertUnwindSafe(|| run(file))).is_err() {
+                eprintln!("The solver panicked");
+            }
+        }
+
+        let names = files
+            .iter()
+            .map(|file| file.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" and ");
+        eprintln!("\nWatching {names} for changes");
+        wait_for_change(files, &mut last);
+        eprintln!();
+    }
+}
@@ -0,0 +1,22 @@
+use advent_of_code_2024::memory::{self, CountingAllocator};
+
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+
+// the only test of this binary, as counting covers every thread of the process
+#[test]
+fn test() {
+    memory::enable();
+
+    let (buffer, stats) = memory::measure(|| vec![1u8; 4096]);
+    let stats = stats.unwrap();
+    assert_eq!(buffer.len(), 4096);
+    assert!(stats.allocations >= 1);
+    assert!(stats.bytes >= 4096);
+    assert!(stats.peak_heap_bytes >= 4096);
+
+    let (_, stats) = memory::measure(|| drop(buffer));
+    assert_eq!(stats.unwrap().peak_heap_bytes, 0, "Freeing memory isn't a peak");
+}
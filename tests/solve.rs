@@ -0,0 +1,15 @@
+use std::io::Cursor;
+use advent_of_code_2024::solve;
+
+
+#[test]
+fn test() {
+    let input = Cursor::new("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n");
+    let (part1, part2) = solve(2024, 1, input).unwrap();
+    assert_eq!(part1.to_string(), "11");
+    assert_eq!(part2.unwrap().to_string(), "31");
+
+    assert!(solve(2024, 26, Cursor::new("")).is_err());
+    // panics of the solver are errors too
+    assert!(solve(2024, 16, Cursor::new("")).is_err());
+}